@@ -18,10 +18,46 @@ libcamera_stream_configuration_t *libcamera_camera_configuration_at(libcamera_ca
     }
 }
 
+libcamera_stream_configuration_t *libcamera_camera_configuration_add_configuration(libcamera_camera_configuration_t* config, libcamera_camera_t *cam, enum libcamera_stream_role role) {
+    // Seed the stream from a generated configuration, which lists the formats pipelines rely on during validation.
+    std::unique_ptr<libcamera::CameraConfiguration> generated = cam->get()->generateConfiguration({ (libcamera::StreamRole)role });
+    if (!generated || generated->empty())
+        return nullptr;
+
+    config->addConfiguration(generated->at(0));
+    return &config->at(config->size() - 1);
+}
+
+libcamera_camera_configuration_t *libcamera_camera_configuration_remove_configuration(const libcamera_camera_configuration_t* config, libcamera_camera_t *cam, size_t index) {
+    if (index >= config->size())
+        return nullptr;
+
+    // libcamera does not provide a way to remove stream configurations, rebuild the configuration without it instead.
+    std::unique_ptr<libcamera::CameraConfiguration> rebuilt = cam->get()->generateConfiguration({});
+    if (!rebuilt)
+        return nullptr;
+
+    for (size_t i = 0; i < config->size(); i++) {
+        if (i != index)
+            rebuilt->addConfiguration(config->at(i));
+    }
+    rebuilt->sensorConfig = config->sensorConfig;
+    rebuilt->orientation = config->orientation;
+    return rebuilt.release();
+}
+
 libcamera_camera_configuration_status_t libcamera_camera_configuration_validate(libcamera_camera_configuration_t* config) {
     return config->validate();
 }
 
+libcamera_orientation_t libcamera_camera_configuration_get_orientation(const libcamera_camera_configuration_t* config) {
+    return config->orientation;
+}
+
+void libcamera_camera_configuration_set_orientation(libcamera_camera_configuration_t* config, libcamera_orientation_t orientation) {
+    config->orientation = orientation;
+}
+
 libcamera_camera_t* libcamera_camera_copy(libcamera_camera_t *cam) {
     const libcamera_camera_t& ptr = *cam;
     return new libcamera_camera_t(ptr);
@@ -50,6 +86,44 @@ void libcamera_camera_request_completed_disconnect(libcamera_camera_t *cam, libc
     delete handle;
 }
 
+libcamera_callback_handle_t *libcamera_camera_buffer_completed_connect(libcamera_camera_t *cam, libcamera_buffer_completed_cb_t *callback, void *data) {
+    libcamera_callback_handle_t *handle = new libcamera_callback_handle_t {};
+
+    cam->get()->bufferCompleted.connect(handle, [=](libcamera::Request *request, libcamera::FrameBuffer *buffer) {
+        const libcamera::Stream *stream = nullptr;
+        for (const auto &[s, b] : request->buffers()) {
+            if (b == buffer) {
+                stream = s;
+                break;
+            }
+        }
+
+        callback(data, request, stream, buffer);
+    });
+
+    return handle;
+}
+
+void libcamera_camera_buffer_completed_disconnect(libcamera_camera_t *cam, libcamera_callback_handle_t *handle) {
+    cam->get()->bufferCompleted.disconnect(handle);
+    delete handle;
+}
+
+libcamera_callback_handle_t *libcamera_camera_disconnected_connect(libcamera_camera_t *cam, libcamera_camera_disconnected_cb_t *callback, void *data) {
+    libcamera_callback_handle_t *handle = new libcamera_callback_handle_t {};
+
+    cam->get()->disconnected.connect(handle, [=]() {
+        callback(data);
+    });
+
+    return handle;
+}
+
+void libcamera_camera_disconnected_disconnect(libcamera_camera_t *cam, libcamera_callback_handle_t *handle) {
+    cam->get()->disconnected.disconnect(handle);
+    delete handle;
+}
+
 int libcamera_camera_acquire(libcamera_camera_t *cam) {
     return cam->get()->acquire();
 }
@@ -71,6 +145,10 @@ libcamera_camera_configuration_t *libcamera_camera_generate_configuration(libcam
     return cam->get()->generateConfiguration(roles_vec).release();
 }
 
+libcamera_camera_configuration_t *libcamera_camera_generate_empty_configuration(libcamera_camera_t *cam) {
+    return cam->get()->generateConfiguration({}).release();
+}
+
 int libcamera_camera_configure(libcamera_camera_t *cam, libcamera_camera_configuration_t *config) {
     return cam->get()->configure(config);
 }
@@ -96,11 +174,69 @@ libcamera_sensor_configuration_t *libcamera_sensor_configuration_create()
     return new libcamera_sensor_configuration_t();
 }
 
+libcamera_sensor_configuration_t *libcamera_sensor_configuration_copy(const libcamera_sensor_configuration_t *config)
+{
+    return new libcamera_sensor_configuration_t(*config);
+}
+
+bool libcamera_sensor_configuration_is_valid(const libcamera_sensor_configuration_t *config)
+{
+    return config->isValid();
+}
+
+unsigned int libcamera_sensor_configuration_get_bit_depth(const libcamera_sensor_configuration_t *config)
+{
+    return config->bitDepth;
+}
+
 void libcamera_sensor_configuration_set_bit_depth(libcamera_sensor_configuration_t *config, unsigned int bit_depth)
 {
     config->bitDepth = bit_depth;
 }
 
+void libcamera_sensor_configuration_get_analog_crop(const libcamera_sensor_configuration_t *config, libcamera_rectangle_t *crop)
+{
+    *crop = config->analogCrop;
+}
+
+void libcamera_sensor_configuration_set_analog_crop(libcamera_sensor_configuration_t *config, const libcamera_rectangle_t *crop)
+{
+    config->analogCrop = *crop;
+}
+
+void libcamera_sensor_configuration_get_binning(const libcamera_sensor_configuration_t *config, unsigned int *bin_x, unsigned int *bin_y)
+{
+    *bin_x = config->binning.binX;
+    *bin_y = config->binning.binY;
+}
+
+void libcamera_sensor_configuration_set_binning(libcamera_sensor_configuration_t *config, unsigned int bin_x, unsigned int bin_y)
+{
+    config->binning.binX = bin_x;
+    config->binning.binY = bin_y;
+}
+
+void libcamera_sensor_configuration_get_skipping(const libcamera_sensor_configuration_t *config, unsigned int *x_odd_inc, unsigned int *x_even_inc, unsigned int *y_odd_inc, unsigned int *y_even_inc)
+{
+    *x_odd_inc = config->skipping.xOddInc;
+    *x_even_inc = config->skipping.xEvenInc;
+    *y_odd_inc = config->skipping.yOddInc;
+    *y_even_inc = config->skipping.yEvenInc;
+}
+
+void libcamera_sensor_configuration_set_skipping(libcamera_sensor_configuration_t *config, unsigned int x_odd_inc, unsigned int x_even_inc, unsigned int y_odd_inc, unsigned int y_even_inc)
+{
+    config->skipping.xOddInc = x_odd_inc;
+    config->skipping.xEvenInc = x_even_inc;
+    config->skipping.yOddInc = y_odd_inc;
+    config->skipping.yEvenInc = y_even_inc;
+}
+
+void libcamera_sensor_configuration_get_output_size(const libcamera_sensor_configuration_t *config, libcamera_size_t *size)
+{
+    *size = config->outputSize;
+}
+
 void libcamera_sensor_configuration_set_output_size(libcamera_sensor_configuration_t *config, unsigned int width, unsigned int height)
 {
     config->outputSize = libcamera::Size(width, height);
@@ -111,6 +247,19 @@ void libcamera_camera_set_sensor_configuration(libcamera_camera_configuration_t
     config->sensorConfig = *sensor_config;
 }
 
+libcamera_sensor_configuration_t *libcamera_camera_get_sensor_configuration(const libcamera_camera_configuration_t *config)
+{
+    if (!config->sensorConfig)
+        return nullptr;
+
+    return new libcamera_sensor_configuration_t(*config->sensorConfig);
+}
+
+void libcamera_camera_clear_sensor_configuration(libcamera_camera_configuration_t *config)
+{
+    config->sensorConfig.reset();
+}
+
 void libcamera_sensor_configuration_destroy(libcamera_sensor_configuration_t *config) {
     delete config;
 }
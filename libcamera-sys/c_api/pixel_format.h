@@ -31,6 +31,11 @@ typedef struct libcamera_pixel_formats libcamera_pixel_formats_t;
 /// @return A heap allocated null-terminated string, that has to be deallocated with free()
 char *libcamera_pixel_format_str(const libcamera_pixel_format_t *format);
 
+/// @brief Parses pixel format from its string representation, as returned by libcamera_pixel_format_str()
+/// @param str Null-terminated string
+/// @return Parsed pixel format, or an invalid pixel format with zero fourcc if the string is not recognized
+libcamera_pixel_format_t libcamera_pixel_format_from_str(const char *str);
+
 void libcamera_pixel_formats_destroy(libcamera_pixel_formats_t *formats);
 size_t libcamera_pixel_formats_size(const libcamera_pixel_formats_t *formats);
 libcamera_pixel_format_t libcamera_pixel_formats_get(const libcamera_pixel_formats_t *formats, size_t index);
@@ -24,4 +24,28 @@ libcamera_stream_t *libcamera_stream_configuration_stream(const libcamera_stream
     return config->stream();
 }
 
+bool libcamera_stream_configuration_get_color_space(const libcamera_stream_configuration_t *config, libcamera_color_space_t *color_space) {
+    if (!config->colorSpace)
+        return false;
+
+    color_space->primaries = (enum libcamera_color_space_primaries)config->colorSpace->primaries;
+    color_space->transfer_function = (enum libcamera_color_space_transfer_function)config->colorSpace->transferFunction;
+    color_space->ycbcr_encoding = (enum libcamera_color_space_ycbcr_encoding)config->colorSpace->ycbcrEncoding;
+    color_space->range = (enum libcamera_color_space_range)config->colorSpace->range;
+    return true;
+}
+
+void libcamera_stream_configuration_set_color_space(libcamera_stream_configuration_t *config, const libcamera_color_space_t *color_space) {
+    if (!color_space) {
+        config->colorSpace.reset();
+        return;
+    }
+
+    config->colorSpace = libcamera::ColorSpace(
+        (libcamera::ColorSpace::Primaries)color_space->primaries,
+        (libcamera::ColorSpace::TransferFunction)color_space->transfer_function,
+        (libcamera::ColorSpace::YcbcrEncoding)color_space->ycbcr_encoding,
+        (libcamera::ColorSpace::Range)color_space->range);
+}
+
 }
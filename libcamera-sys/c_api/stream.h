@@ -4,8 +4,43 @@
 #include "geometry.h"
 #include "pixel_format.h"
 
+#include <stdbool.h>
 #include <stddef.h>
 
+enum libcamera_color_space_primaries {
+    LIBCAMERA_COLOR_SPACE_PRIMARIES_RAW = 0,
+    LIBCAMERA_COLOR_SPACE_PRIMARIES_SMPTE170M,
+    LIBCAMERA_COLOR_SPACE_PRIMARIES_REC709,
+    LIBCAMERA_COLOR_SPACE_PRIMARIES_REC2020,
+};
+
+enum libcamera_color_space_transfer_function {
+    LIBCAMERA_COLOR_SPACE_TRANSFER_FUNCTION_LINEAR = 0,
+    LIBCAMERA_COLOR_SPACE_TRANSFER_FUNCTION_SRGB,
+    LIBCAMERA_COLOR_SPACE_TRANSFER_FUNCTION_REC709,
+};
+
+enum libcamera_color_space_ycbcr_encoding {
+    LIBCAMERA_COLOR_SPACE_YCBCR_ENCODING_NONE = 0,
+    LIBCAMERA_COLOR_SPACE_YCBCR_ENCODING_REC601,
+    LIBCAMERA_COLOR_SPACE_YCBCR_ENCODING_REC709,
+    LIBCAMERA_COLOR_SPACE_YCBCR_ENCODING_REC2020,
+};
+
+enum libcamera_color_space_range {
+    LIBCAMERA_COLOR_SPACE_RANGE_FULL = 0,
+    LIBCAMERA_COLOR_SPACE_RANGE_LIMITED,
+};
+
+struct libcamera_color_space {
+    enum libcamera_color_space_primaries primaries;
+    enum libcamera_color_space_transfer_function transfer_function;
+    enum libcamera_color_space_ycbcr_encoding ycbcr_encoding;
+    enum libcamera_color_space_range range;
+};
+
+typedef struct libcamera_color_space libcamera_color_space_t;
+
 struct libcamera_stream_configuration {
     libcamera_pixel_format_t pixel_format;
     libcamera_size_t size;
@@ -21,6 +56,20 @@ typedef libcamera::StreamFormats libcamera_stream_formats_t;
 
 typedef libcamera::StreamConfiguration libcamera_stream_configuration_t;
 
+static_assert(static_cast<int>(libcamera::ColorSpace::Primaries::Raw) == LIBCAMERA_COLOR_SPACE_PRIMARIES_RAW);
+static_assert(static_cast<int>(libcamera::ColorSpace::Primaries::Smpte170m) == LIBCAMERA_COLOR_SPACE_PRIMARIES_SMPTE170M);
+static_assert(static_cast<int>(libcamera::ColorSpace::Primaries::Rec709) == LIBCAMERA_COLOR_SPACE_PRIMARIES_REC709);
+static_assert(static_cast<int>(libcamera::ColorSpace::Primaries::Rec2020) == LIBCAMERA_COLOR_SPACE_PRIMARIES_REC2020);
+static_assert(static_cast<int>(libcamera::ColorSpace::TransferFunction::Linear) == LIBCAMERA_COLOR_SPACE_TRANSFER_FUNCTION_LINEAR);
+static_assert(static_cast<int>(libcamera::ColorSpace::TransferFunction::Srgb) == LIBCAMERA_COLOR_SPACE_TRANSFER_FUNCTION_SRGB);
+static_assert(static_cast<int>(libcamera::ColorSpace::TransferFunction::Rec709) == LIBCAMERA_COLOR_SPACE_TRANSFER_FUNCTION_REC709);
+static_assert(static_cast<int>(libcamera::ColorSpace::YcbcrEncoding::None) == LIBCAMERA_COLOR_SPACE_YCBCR_ENCODING_NONE);
+static_assert(static_cast<int>(libcamera::ColorSpace::YcbcrEncoding::Rec601) == LIBCAMERA_COLOR_SPACE_YCBCR_ENCODING_REC601);
+static_assert(static_cast<int>(libcamera::ColorSpace::YcbcrEncoding::Rec709) == LIBCAMERA_COLOR_SPACE_YCBCR_ENCODING_REC709);
+static_assert(static_cast<int>(libcamera::ColorSpace::YcbcrEncoding::Rec2020) == LIBCAMERA_COLOR_SPACE_YCBCR_ENCODING_REC2020);
+static_assert(static_cast<int>(libcamera::ColorSpace::Range::Full) == LIBCAMERA_COLOR_SPACE_RANGE_FULL);
+static_assert(static_cast<int>(libcamera::ColorSpace::Range::Limited) == LIBCAMERA_COLOR_SPACE_RANGE_LIMITED);
+
 // Read more about this in https://github.com/google/benchmark/issues/552
 #ifdef __GNUC__
 #pragma GCC diagnostic push
@@ -57,6 +106,8 @@ libcamera_size_range_t libcamera_stream_formats_range(const libcamera_stream_for
 
 const libcamera_stream_formats_t *libcamera_stream_configuration_formats(const libcamera_stream_configuration_t *config);
 libcamera_stream_t *libcamera_stream_configuration_stream(const libcamera_stream_configuration_t *config);
+bool libcamera_stream_configuration_get_color_space(const libcamera_stream_configuration_t *config, libcamera_color_space_t *color_space);
+void libcamera_stream_configuration_set_color_space(libcamera_stream_configuration_t *config, const libcamera_color_space_t *color_space);
 
 #ifdef __cplusplus
 }
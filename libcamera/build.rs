@@ -75,9 +75,21 @@ fn main() {
 
     for file in ["controls.rs", "properties.rs"] {
         std::fs::copy(selected_version.join(file), out_path.join(file)).unwrap();
-        print!(
+        println!(
             "cargo:rerun-if-changed={}",
             selected_version.join(file).to_string_lossy()
         );
     }
+
+    // Controls introduced in libcamera 0.5.2, which are not generated for older versions.
+    let controls = std::fs::read_to_string(selected_version.join("controls.rs")).unwrap();
+    for (cfg, variant) in [
+        ("libcamera_sync_controls", "SyncMode = SYNC_MODE"),
+        ("libcamera_frame_wall_clock", "FrameWallClock = FRAME_WALL_CLOCK"),
+    ] {
+        println!("cargo:rustc-check-cfg=cfg({cfg})");
+        if controls.contains(variant) {
+            println!("cargo:rustc-cfg={cfg}");
+        }
+    }
 }
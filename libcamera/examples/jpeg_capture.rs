@@ -83,7 +83,7 @@ fn main() {
         tx.send(req).unwrap();
     });
 
-    cam.start(None).unwrap();
+    let cam = cam.start(None).unwrap();
 
     // Multiple requests can be queued at a time, but for this example we just want a single frame.
     cam.queue_request(reqs.pop().unwrap()).unwrap();
@@ -92,7 +92,7 @@ fn main() {
     });
 
     // TODO: Set `Control::FrameDuration()` here. Blocked on https://github.com/lit-robotics/libcamera-rs/issues/2
-    cam.start(None).unwrap();
+    let cam = cam.start(None).unwrap();
 
     // Enqueue all requests to the camera
     for req in reqs {
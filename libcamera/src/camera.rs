@@ -1,20 +1,30 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     ffi::CStr,
     io,
     marker::PhantomData,
+    mem::MaybeUninit,
     ops::{Deref, DerefMut},
+    os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd},
+    panic::{self, AssertUnwindSafe},
     ptr::NonNull,
-    sync::Mutex,
+    sync::{Arc, Mutex},
+    thread,
 };
 
 use libcamera_sys::*;
 
+#[cfg(feature = "async")]
+use crate::request_stream::RequestStream;
 use crate::{
     control::{ControlInfoMap, ControlList, PropertyList},
+    error::Error,
+    framebuffer::{AsFrameBuffer, FrameBufferRef},
+    geometry::{Rectangle, Size},
+    orientation::Orientation,
     request::Request,
-    stream::{StreamConfigurationRef, StreamRole},
-    utils::Immutable,
+    stream::{Stream, StreamConfigurationRef, StreamRole},
+    utils::{self, Immutable},
 };
 
 /// Status of [CameraConfiguration]
@@ -55,10 +65,37 @@ impl TryFrom<libcamera_camera_configuration_status_t> for CameraConfigurationSta
     }
 }
 
+/// Sensor configuration, which selects a specific sensor readout mode.
+///
+/// Applied with [CameraConfiguration::set_sensor_configuration()]. A valid configuration requires at least bit depth
+/// and output size to be set, see [SensorConfiguration::is_valid()].
 pub struct SensorConfiguration {
     item: NonNull<libcamera_sensor_configuration_t>,
 }
 
+/// Sensor pixel skipping increments for odd and even rows and columns.
+///
+/// All increments equal to 1 mean no skipping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SensorSkipping {
+    pub x_odd_inc: u32,
+    pub x_even_inc: u32,
+    pub y_odd_inc: u32,
+    pub y_even_inc: u32,
+}
+
+impl Default for SensorSkipping {
+    fn default() -> Self {
+        Self {
+            x_odd_inc: 1,
+            x_even_inc: 1,
+            y_odd_inc: 1,
+            y_even_inc: 1,
+        }
+    }
+}
+
 impl SensorConfiguration {
     pub fn new() -> Self {
         let ptr = NonNull::new(unsafe { libcamera_sensor_configuration_create() }).unwrap();
@@ -69,15 +106,113 @@ impl SensorConfiguration {
         Self { item: ptr }
     }
 
+    /// Returns `true` if the configuration is complete and consistent enough to be applied.
+    pub fn is_valid(&self) -> bool {
+        unsafe { libcamera_sensor_configuration_is_valid(self.item.as_ptr()) }
+    }
+
+    pub fn bit_depth(&self) -> u32 {
+        unsafe { libcamera_sensor_configuration_get_bit_depth(self.item.as_ptr()) }
+    }
+
     pub fn set_bit_depth(&mut self, depth: u32) {
         unsafe { libcamera_sensor_configuration_set_bit_depth(self.item.as_ptr(), depth) }
     }
 
+    /// Returns the area of the sensor pixel array, which is read out, in pixel array coordinates.
+    pub fn analog_crop(&self) -> Rectangle {
+        let mut crop = MaybeUninit::uninit();
+        unsafe {
+            libcamera_sensor_configuration_get_analog_crop(self.item.as_ptr(), crop.as_mut_ptr());
+            crop.assume_init()
+        }
+        .into()
+    }
+
+    pub fn set_analog_crop(&mut self, crop: Rectangle) {
+        let crop: libcamera_rectangle_t = crop.into();
+        unsafe { libcamera_sensor_configuration_set_analog_crop(self.item.as_ptr(), &crop) }
+    }
+
+    /// Returns horizontal and vertical binning factors.
+    pub fn binning(&self) -> (u32, u32) {
+        let (mut bin_x, mut bin_y) = (0, 0);
+        unsafe { libcamera_sensor_configuration_get_binning(self.item.as_ptr(), &mut bin_x, &mut bin_y) };
+        (bin_x, bin_y)
+    }
+
+    pub fn set_binning(&mut self, bin_x: u32, bin_y: u32) {
+        unsafe { libcamera_sensor_configuration_set_binning(self.item.as_ptr(), bin_x, bin_y) }
+    }
+
+    pub fn skipping(&self) -> SensorSkipping {
+        let mut skipping = SensorSkipping::default();
+        unsafe {
+            libcamera_sensor_configuration_get_skipping(
+                self.item.as_ptr(),
+                &mut skipping.x_odd_inc,
+                &mut skipping.x_even_inc,
+                &mut skipping.y_odd_inc,
+                &mut skipping.y_even_inc,
+            )
+        };
+        skipping
+    }
+
+    pub fn set_skipping(&mut self, skipping: SensorSkipping) {
+        unsafe {
+            libcamera_sensor_configuration_set_skipping(
+                self.item.as_ptr(),
+                skipping.x_odd_inc,
+                skipping.x_even_inc,
+                skipping.y_odd_inc,
+                skipping.y_even_inc,
+            )
+        }
+    }
+
+    pub fn output_size(&self) -> Size {
+        let mut size = MaybeUninit::uninit();
+        unsafe {
+            libcamera_sensor_configuration_get_output_size(self.item.as_ptr(), size.as_mut_ptr());
+            size.assume_init()
+        }
+        .into()
+    }
+
     pub fn set_output_size(&mut self, width: u32, height: u32) {
         unsafe { libcamera_sensor_configuration_set_output_size(self.item.as_ptr(), width, height) }
     }
 }
 
+impl Clone for SensorConfiguration {
+    fn clone(&self) -> Self {
+        Self::from_ptr(NonNull::new(unsafe { libcamera_sensor_configuration_copy(self.item.as_ptr()) }).unwrap())
+    }
+}
+
+impl PartialEq for SensorConfiguration {
+    fn eq(&self, other: &Self) -> bool {
+        self.bit_depth() == other.bit_depth()
+            && self.analog_crop() == other.analog_crop()
+            && self.binning() == other.binning()
+            && self.skipping() == other.skipping()
+            && self.output_size() == other.output_size()
+    }
+}
+
+impl core::fmt::Debug for SensorConfiguration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SensorConfiguration")
+            .field("bit_depth", &self.bit_depth())
+            .field("analog_crop", &self.analog_crop())
+            .field("binning", &self.binning())
+            .field("skipping", &self.skipping())
+            .field("output_size", &self.output_size())
+            .finish()
+    }
+}
+
 impl Default for SensorConfiguration {
     fn default() -> Self {
         Self::new()
@@ -90,6 +225,18 @@ impl Drop for SensorConfiguration {
     }
 }
 
+/// Outcome of sensor configuration validation, see [CameraConfiguration::validate_sensor_configuration()].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensorConfigurationStatus {
+    /// Sensor configuration was accepted as is.
+    Accepted,
+    /// Sensor configuration was adjusted by libcamera, read it back with
+    /// [CameraConfiguration::sensor_configuration()].
+    Adjusted,
+    /// Sensor configuration is invalid or the whole camera configuration was rejected.
+    Invalid,
+}
+
 /// Camera configuration.
 ///
 /// Contains [StreamConfigurationRef] for each stream used by the camera.
@@ -122,10 +269,98 @@ impl CameraConfiguration {
         NonNull::new(ptr).map(|p| unsafe { StreamConfigurationRef::from_ptr(p) })
     }
 
+    /// Appends a stream configuration with the defaults `cam` generates for `role` and returns it for further
+    /// adjustment.
+    ///
+    /// The added stream lists the [formats](StreamConfigurationRef::formats) available for the role, which pipelines
+    /// rely on during validation. `cam` must be the camera that generated this configuration. Returns `None` if it can
+    /// not generate configuration for `role`.
+    pub fn add_stream(&mut self, cam: &Camera<'_>, role: StreamRole) -> Option<StreamConfigurationRef<'_>> {
+        let ptr = unsafe {
+            libcamera_camera_configuration_add_configuration(self.ptr.as_ptr(), cam.ptr.as_ptr(), role.into())
+        };
+        NonNull::new(ptr).map(|p| unsafe { StreamConfigurationRef::from_ptr(p) })
+    }
+
+    /// Removes stream configuration at `index`, shifting the following streams down.
+    ///
+    /// libcamera can not remove streams in place, so the configuration is rebuilt from the remaining streams, sensor
+    /// configuration and orientation, which requires `cam` that generated this configuration. Returns `false` if there
+    /// is no stream at `index` or the configuration could not be rebuilt.
+    pub fn remove_stream(&mut self, cam: &Camera<'_>, index: usize) -> bool {
+        let ptr = unsafe {
+            libcamera_camera_configuration_remove_configuration(self.ptr.as_ptr(), cam.ptr.as_ptr(), index as _)
+        };
+        match NonNull::new(ptr) {
+            Some(ptr) => {
+                *self = unsafe { Self::from_ptr(ptr) };
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn set_sensor_configuration(&mut self, mode: SensorConfiguration) {
         unsafe { libcamera_camera_set_sensor_configuration(self.ptr.as_ptr(), mode.item.as_ptr()) }
     }
 
+    /// Removes the sensor configuration, so that libcamera selects the sensor mode on its own.
+    pub fn clear_sensor_configuration(&mut self) {
+        unsafe { libcamera_camera_clear_sensor_configuration(self.ptr.as_ptr()) }
+    }
+
+    /// Returns a copy of the sensor configuration, if one is set.
+    ///
+    /// After [CameraConfiguration::validate()] it reflects any adjustments made by libcamera.
+    pub fn sensor_configuration(&self) -> Option<SensorConfiguration> {
+        NonNull::new(unsafe { libcamera_camera_get_sensor_configuration(self.ptr.as_ptr()) })
+            .map(SensorConfiguration::from_ptr)
+    }
+
+    /// Validates camera configuration and reports how libcamera handled the requested sensor configuration.
+    ///
+    /// Returns the status of the whole configuration together with the status of the sensor configuration, which is
+    /// `None` if no sensor configuration is set. See [CameraConfiguration::validate_with_report()] for other fields.
+    pub fn validate_sensor_configuration(&mut self) -> (CameraConfigurationStatus, Option<SensorConfigurationStatus>) {
+        let requested = self.sensor_configuration();
+        let report = self.validate_with_report();
+
+        let sensor = requested.map(|requested| match report.sensor_configuration {
+            _ if !requested.is_valid() || report.status.is_invalid() => SensorConfigurationStatus::Invalid,
+            None => SensorConfigurationStatus::Accepted,
+            Some(_) => SensorConfigurationStatus::Adjusted,
+        });
+        (report.status, sensor)
+    }
+
+    /// Returns the requested image orientation.
+    ///
+    /// After [CameraConfiguration::validate()] it reflects the orientation libcamera is able to produce.
+    pub fn orientation(&self) -> Orientation {
+        unsafe { libcamera_camera_configuration_get_orientation(self.ptr.as_ptr()) }
+            .try_into()
+            .unwrap()
+    }
+
+    /// Requests image orientation, relative to the natural orientation of the camera sensor.
+    ///
+    /// libcamera applies the orientation with sensor flips where possible. Use
+    /// [CameraConfiguration::validate_orientation()] to find out whether it can be honored.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        unsafe { libcamera_camera_configuration_set_orientation(self.ptr.as_ptr(), orientation.into()) }
+    }
+
+    /// Validates camera configuration and reports whether libcamera adjusted the requested orientation.
+    ///
+    /// Returns the status of the whole configuration together with the adjusted orientation, which is `None` if the
+    /// requested one was kept or the configuration is invalid. The transformation left to apply in software is
+    /// `adjusted.inverse().compose(requested)`. See [CameraConfiguration::validate_with_report()] for other fields.
+    pub fn validate_orientation(&mut self) -> (CameraConfigurationStatus, Option<Orientation>) {
+        let report = self.validate_with_report();
+        let adjusted = report.orientation.filter(|_| !report.status.is_invalid());
+        (report.status, adjusted.map(|change| change.to))
+    }
+
     /// Returns number of streams within camera configuration.
     pub fn len(&self) -> usize {
         unsafe { libcamera_camera_configuration_size(self.ptr.as_ptr()) }
@@ -137,6 +372,8 @@ impl CameraConfiguration {
     }
 
     /// Validates camera configuration.
+    ///
+    /// Use [CameraConfiguration::validate_with_report()] to find out which fields were adjusted.
     pub fn validate(&mut self) -> CameraConfigurationStatus {
         unsafe { libcamera_camera_configuration_validate(self.ptr.as_ptr()) }
             .try_into()
@@ -216,11 +453,21 @@ impl<'d> Camera<'d> {
         NonNull::new(cfg).map(|p| unsafe { CameraConfiguration::from_ptr(p) })
     }
 
+    /// Generates camera configuration without any streams.
+    ///
+    /// Streams can be added with [CameraConfiguration::add_stream()], which allows building multi-stream
+    /// configurations explicitly instead of relying on [StreamRole] defaults. Configuration must be validated before it
+    /// is applied.
+    pub fn generate_empty_configuration(&self) -> Option<CameraConfiguration> {
+        let cfg = unsafe { libcamera_camera_generate_empty_configuration(self.ptr.as_ptr()) };
+        NonNull::new(cfg).map(|p| unsafe { CameraConfiguration::from_ptr(p) })
+    }
+
     /// Acquires exclusive rights to the camera, which allows changing configuration and capturing.
-    pub fn acquire(&self) -> io::Result<ActiveCamera<'d>> {
+    pub fn acquire(&self) -> Result<ActiveCamera<'d>, Error> {
         let ret = unsafe { libcamera_camera_acquire(self.ptr.as_ptr()) };
         if ret < 0 {
-            Err(io::Error::from_raw_os_error(ret))
+            Err(Error::from_errno("Camera::acquire", -ret))
         } else {
             Ok(unsafe { ActiveCamera::from_ptr(NonNull::new(libcamera_camera_copy(self.ptr.as_ptr())).unwrap()) })
         }
@@ -234,39 +481,259 @@ impl Drop for Camera<'_> {
 }
 
 extern "C" fn camera_request_completed_cb(ptr: *mut core::ffi::c_void, req: *mut libcamera_request_t) {
-    let mut state = unsafe { &*(ptr as *const Mutex<ActiveCameraState<'_>>) }
-        .lock()
-        .unwrap();
-    let req = state.requests.remove(&req).unwrap();
+    let mut state = utils::lock(unsafe { &*(ptr as *const Mutex<ActiveCameraState<'_>>) });
+    // Every queued request is kept in the map until libcamera returns it, anything else can not be handed to the user.
+    let Some(mut req) = state.requests.remove(&req) else {
+        state.unknown_request = true;
+        return;
+    };
+
+    if state.disconnected {
+        req.cancelled = true;
+    }
+    state.complete(req);
+}
+
+extern "C" fn camera_buffer_completed_cb(
+    ptr: *mut core::ffi::c_void,
+    req: *mut libcamera_request_t,
+    stream: *const libcamera_stream_t,
+    fb: *mut libcamera_framebuffer_t,
+) {
+    let mut state = utils::lock(unsafe { &*(ptr as *const Mutex<ActiveCameraState<'_>>) });
+    let state = &mut *state;
+
+    let (Some(req), Some(stream), Some(fb)) = (
+        state.requests.get(&req),
+        NonNull::new(stream.cast_mut()),
+        NonNull::new(fb),
+    ) else {
+        return;
+    };
 
-    if let Some(cb) = &mut state.request_completed_cb {
-        cb(req);
+    if let Some(cb) = &mut state.buffer_completed_cb {
+        let stream = unsafe { Stream::from_ptr(stream) };
+        let fb = unsafe { FrameBufferRef::from_ptr(fb) };
+        let res = panic::catch_unwind(AssertUnwindSafe(|| cb(req, stream, &fb)));
+        state.record_panic("buffer completed", res);
     }
 }
 
+extern "C" fn camera_disconnected_cb(ptr: *mut core::ffi::c_void) {
+    let mut state = utils::lock(unsafe { &*(ptr as *const Mutex<ActiveCameraState<'_>>) });
+    state.disconnected = true;
+
+    if let Some(cb) = &mut state.disconnected_cb {
+        let res = panic::catch_unwind(AssertUnwindSafe(cb));
+        state.record_panic("disconnected", res);
+    }
+}
+
+pub(crate) type RequestCompletedCallback<'d> = Box<dyn FnMut(Request) + Send + 'd>;
+type BufferCompletedCallback<'d> = Box<dyn FnMut(&Request, Stream, &dyn AsFrameBuffer) + Send + 'd>;
+
 #[derive(Default)]
 struct ActiveCameraState<'d> {
     /// List of queued requests that are yet to be executed.
     /// Used to temporarily store [Request] before returning it back to the user.
     requests: HashMap<*mut libcamera_request_t, Request>,
     /// Callback for libcamera `requestCompleted` signal.
-    request_completed_cb: Option<Box<dyn FnMut(Request) + Send + 'd>>,
+    request_completed_cb: Option<RequestCompletedCallback<'d>>,
+    /// Callback for libcamera `bufferCompleted` signal.
+    buffer_completed_cb: Option<BufferCompletedCallback<'d>>,
+    /// Callback for libcamera `disconnected` signal.
+    disconnected_cb: Option<Box<dyn FnMut() + Send + 'd>>,
+    /// Set once the camera has been disconnected from the system.
+    disconnected: bool,
+    /// Pollable queue of completed requests, replaces request completed callback when enabled.
+    completion_queue: Option<CompletionQueue>,
+    /// Message of a panic caught in a user callback, which is yet to be reported to the application.
+    callback_panic: Option<String>,
+    /// Set when libcamera completed a request, which is not in `requests`, until it is reported to the application.
+    unknown_request: bool,
 }
 
-/// An active instance of a camera.
+impl ActiveCameraState<'_> {
+    /// Passes completed request either to the completion queue, if enabled, or to the request completed callback.
+    fn complete(&mut self, req: Request) {
+        if let Some(queue) = &mut self.completion_queue {
+            queue.push(req);
+        } else if let Some(cb) = &mut self.request_completed_cb {
+            let res = panic::catch_unwind(AssertUnwindSafe(|| cb(req)));
+            self.record_panic("request completed", res);
+        }
+    }
+
+    /// Stores the panic of a user callback, so that it does not unwind into libcamera.
+    ///
+    /// Only the first panic is kept until it is reported by [ActiveCamera::queue_request()].
+    fn record_panic(&mut self, callback: &str, res: thread::Result<()>) {
+        utils::record_panic(&mut self.callback_panic, callback, res);
+    }
+}
+
+/// Queue of completed requests, which signals an eventfd whenever it is not empty.
+pub(crate) struct CompletionQueue {
+    fd: Arc<OwnedFd>,
+    requests: VecDeque<Request>,
+}
+
+impl CompletionQueue {
+    fn push(&mut self, req: Request) {
+        self.requests.push_back(req);
+
+        let val: u64 = 1;
+        unsafe {
+            libc::write(
+                self.fd.as_raw_fd(),
+                &val as *const u64 as *const _,
+                core::mem::size_of::<u64>(),
+            )
+        };
+    }
+
+    fn pop(&mut self) -> Option<Request> {
+        let req = self.requests.pop_front();
+
+        // Reset eventfd counter once the queue is drained so that it is no longer reported as readable.
+        if self.requests.is_empty() {
+            let mut val: u64 = 0;
+            unsafe {
+                libc::read(
+                    self.fd.as_raw_fd(),
+                    &mut val as *mut u64 as *mut _,
+                    core::mem::size_of::<u64>(),
+                )
+            };
+        }
+
+        req
+    }
+}
+
+/// Handle of the completion queue enabled by [ActiveCamera::enable_completion_queue()].
 ///
-/// This gives exclusive access to the camera and allows capturing and modifying configuration.
+/// Its eventfd becomes readable whenever there are completed requests pending, which are then retrieved with
+/// [ActiveCamera::try_next_completed()]. The eventfd stays open as long as either the handle or the camera exists.
+#[derive(Debug, Clone)]
+pub struct CompletionQueueHandle {
+    fd: Arc<OwnedFd>,
+}
+
+impl AsFd for CompletionQueueHandle {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+}
+
+impl AsRawFd for CompletionQueueHandle {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+/// [ActiveCamera] state, in which the camera is not capturing.
+///
+/// Camera can be configured in this state and then started with [ActiveCamera::start()].
+#[derive(Debug)]
+pub enum Stopped {}
+
+/// [ActiveCamera] state, in which the camera is capturing.
+///
+/// Requests can only be queued in this state. Camera is stopped with [ActiveCamera::stop()].
+#[derive(Debug)]
+pub enum Running {}
+
+/// A capturing [ActiveCamera], obtained by [ActiveCamera::start()].
+pub type RunningCamera<'d> = ActiveCamera<'d, Running>;
+
+/// Error returned when [ActiveCamera] fails to [start](ActiveCamera::start) or [stop](ActiveCamera::stop).
+///
+/// The camera is given back in its original state, so that it is not lost and the operation can be retried.
+#[derive(thiserror::Error)]
+#[error("{error}")]
+pub struct StateTransitionError<C> {
+    /// Camera in the state before the failed transition.
+    pub camera: C,
+    /// Underlying error.
+    #[source]
+    pub error: Error,
+}
+
+impl<C> core::fmt::Debug for StateTransitionError<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StateTransitionError")
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C> From<StateTransitionError<C>> for Error {
+    fn from(e: StateTransitionError<C>) -> Self {
+        e.error
+    }
+}
+
+/// Error returned when [ActiveCamera::queue_request()] fails.
 ///
-/// Obtained by [Camera::acquire()].
-pub struct ActiveCamera<'d> {
+/// The rejected request is given back, so that it can be queued again or its buffers recycled.
+#[derive(Debug, thiserror::Error)]
+#[error("{error}")]
+pub struct QueueRequestError {
+    /// Request that failed to be queued.
+    pub request: Request,
+    /// Underlying error.
+    #[source]
+    pub error: Error,
+}
+
+impl From<QueueRequestError> for Error {
+    fn from(e: QueueRequestError) -> Self {
+        e.error
+    }
+}
+
+/// Owned camera resources, released on drop regardless of the [ActiveCamera] state.
+struct ActiveCameraInner<'d> {
     cam: Camera<'d>,
     /// Handle to disconnect `requestCompleted` signal.
     request_completed_handle: *mut libcamera_callback_handle_t,
+    /// Handle to disconnect `bufferCompleted` signal.
+    buffer_completed_handle: *mut libcamera_callback_handle_t,
+    /// Handle to disconnect `disconnected` signal.
+    disconnected_handle: *mut libcamera_callback_handle_t,
     /// Internal state that is shared with callback handlers.
     state: Box<Mutex<ActiveCameraState<'d>>>,
+    /// Eventfd of the completion queue, if enabled.
+    completion_fd: Option<Arc<OwnedFd>>,
+}
+
+impl Drop for ActiveCameraInner<'_> {
+    fn drop(&mut self) {
+        let ptr = self.cam.ptr.as_ptr();
+        unsafe {
+            libcamera_camera_request_completed_disconnect(ptr, self.request_completed_handle);
+            libcamera_camera_buffer_completed_disconnect(ptr, self.buffer_completed_handle);
+            libcamera_camera_disconnected_disconnect(ptr, self.disconnected_handle);
+            libcamera_camera_stop(ptr);
+            libcamera_camera_release(ptr);
+        }
+    }
 }
 
-impl<'d> ActiveCamera<'d> {
+/// An active instance of a camera.
+///
+/// This gives exclusive access to the camera and allows capturing and modifying configuration.
+///
+/// Obtained by [Camera::acquire()] in the [Stopped] state, which allows configuring the camera. Capture is started by
+/// [ActiveCamera::start()], which turns it into a [RunningCamera]. Only a [RunningCamera] can queue requests and it
+/// must be stopped by [ActiveCamera::stop()] before the camera can be reconfigured.
+pub struct ActiveCamera<'d, S = Stopped> {
+    inner: ActiveCameraInner<'d>,
+    _state: PhantomData<S>,
+}
+
+impl<'d> ActiveCamera<'d, Stopped> {
     pub(crate) unsafe fn from_ptr(ptr: NonNull<libcamera_camera_t>) -> Self {
         let mut state = Box::new(Mutex::new(ActiveCameraState::default()));
 
@@ -279,114 +746,327 @@ impl<'d> ActiveCamera<'d> {
             )
         };
 
+        let buffer_completed_handle = unsafe {
+            libcamera_camera_buffer_completed_connect(
+                ptr.as_ptr(),
+                Some(camera_buffer_completed_cb),
+                // state is valid for the lifetime of `ActiveCamera` and this callback will be disconnected on drop.
+                state.as_mut() as *mut Mutex<ActiveCameraState<'_>> as *mut _,
+            )
+        };
+
+        let disconnected_handle = unsafe {
+            libcamera_camera_disconnected_connect(
+                ptr.as_ptr(),
+                Some(camera_disconnected_cb),
+                // state is valid for the lifetime of `ActiveCamera` and this callback will be disconnected on drop.
+                state.as_mut() as *mut Mutex<ActiveCameraState<'_>> as *mut _,
+            )
+        };
+
         Self {
-            cam: Camera::from_ptr(ptr),
-            request_completed_handle,
-            state,
+            inner: ActiveCameraInner {
+                cam: Camera::from_ptr(ptr),
+                request_completed_handle,
+                buffer_completed_handle,
+                disconnected_handle,
+                state,
+                completion_fd: None,
+            },
+            _state: PhantomData,
         }
     }
 
-    /// Sets a callback for completed camera requests.
-    ///
-    /// Callback is executed in the libcamera thread context so it is best to setup a channel to send all requests for
-    /// processing elsewhere.
-    ///
-    /// Only one callback can be set at a time. If there was a previously set callback, it will be discarded when
-    /// setting a new one.
-    pub fn on_request_completed(&mut self, cb: impl FnMut(Request) + Send + 'd) {
-        let mut state = self.state.lock().unwrap();
-        state.request_completed_cb = Some(Box::new(cb));
-    }
-
     /// Applies camera configuration.
     ///
     /// Default configuration can be obtained from [Camera::generate_configuration()] and then adjusted as needed.
-    pub fn configure(&mut self, config: &mut CameraConfiguration) -> io::Result<()> {
+    pub fn configure(&mut self, config: &mut CameraConfiguration) -> Result<(), Error> {
         let ret = unsafe { libcamera_camera_configure(self.ptr.as_ptr(), config.ptr.as_ptr()) };
         if ret < 0 {
-            Err(io::Error::from_raw_os_error(ret))
+            Err(Error::from_errno("ActiveCamera::configure", -ret))
         } else {
             Ok(())
         }
     }
 
-    /// Creates a capture [`Request`].
-    ///
-    /// To perform a capture, it must firstly be initialized by attaching a framebuffer with [Request::add_buffer()] and
-    /// then queued for execution by [ActiveCamera::queue_request()].
-    ///
-    /// # Arguments
+    /// Starts camera capture session.
     ///
-    /// * `cookie` - An optional user-provided u64 identifier that can be used to uniquely identify request in request
-    ///   completed callback.
-    pub fn create_request(&mut self, cookie: Option<u64>) -> Option<Request> {
-        let req = unsafe { libcamera_camera_create_request(self.ptr.as_ptr(), cookie.unwrap_or(0)) };
-        NonNull::new(req).map(|p| unsafe { Request::from_ptr(p) })
+    /// Consumes the camera and returns a [RunningCamera], which permits [ActiveCamera::queue_request()]. Camera
+    /// configuration can no longer be changed until the camera is stopped. On failure the camera is returned back
+    /// within [StateTransitionError].
+    pub fn start(
+        self,
+        controls: Option<&ControlList>,
+    ) -> Result<RunningCamera<'d>, StateTransitionError<ActiveCamera<'d, Stopped>>> {
+        if self.is_disconnected() {
+            return Err(StateTransitionError {
+                camera: self,
+                error: Error::Disconnected {
+                    op: "ActiveCamera::start",
+                },
+            });
+        }
+
+        let ctrl_ptr = controls.map(|c| c.ptr()).unwrap_or(core::ptr::null_mut());
+        let ret = unsafe { libcamera_camera_start(self.ptr.as_ptr(), ctrl_ptr) };
+        if ret < 0 {
+            Err(StateTransitionError {
+                camera: self,
+                error: Error::from_errno("ActiveCamera::start", -ret),
+            })
+        } else {
+            Ok(self.into_state())
+        }
     }
+}
 
+impl<'d> ActiveCamera<'d, Running> {
     /// Queues [`Request`] for execution. Completed requests are returned in request completed callback, set by the
     /// `ActiveCamera::on_request_completed()`.
     ///
     /// Requests that do not have attached framebuffers are invalid and are rejected without being queued.
-    pub fn queue_request(&self, req: Request) -> io::Result<()> {
+    ///
+    /// If the request is rejected, it is returned back within [QueueRequestError].
+    ///
+    /// Panics in user callbacks are caught and do not propagate into libcamera. Instead, the next call fails with
+    /// [Error::CallbackPanicked] once, without queueing the request. Likewise, a completion of a request which was not
+    /// queued by this camera is reported once with [Error::UnknownRequest].
+    pub fn queue_request(&self, req: Request) -> Result<(), QueueRequestError> {
         let ptr = req.ptr.as_ptr();
-        self.state.lock().unwrap().requests.insert(ptr, req);
+        // Lock is held while queueing so that the camera can not be marked disconnected in the meantime. libcamera
+        // completes requests asynchronously in its own thread, thus this does not deadlock.
+        let mut state = utils::lock(&self.inner.state);
+        if let Some(message) = state.callback_panic.take() {
+            return Err(QueueRequestError {
+                request: req,
+                error: Error::CallbackPanicked {
+                    op: "ActiveCamera::queue_request",
+                    message,
+                },
+            });
+        }
+        if core::mem::take(&mut state.unknown_request) {
+            return Err(QueueRequestError {
+                request: req,
+                error: Error::UnknownRequest {
+                    op: "ActiveCamera::queue_request",
+                },
+            });
+        }
+        if state.disconnected {
+            return Err(QueueRequestError {
+                request: req,
+                error: Error::Disconnected {
+                    op: "ActiveCamera::queue_request",
+                },
+            });
+        }
+        state.requests.insert(ptr, req);
 
         let ret = unsafe { libcamera_camera_queue_request(self.ptr.as_ptr(), ptr) };
 
         if ret < 0 {
-            Err(io::Error::from_raw_os_error(ret))
+            // libcamera did not take the request, so it will never be completed.
+            let req = state.requests.remove(&ptr).unwrap();
+            Err(QueueRequestError {
+                request: req,
+                error: Error::from_errno("ActiveCamera::queue_request", -ret),
+            })
         } else {
             Ok(())
         }
     }
 
-    /// Starts camera capture session.
+    /// Stops camera capture session.
     ///
-    /// Once started, [ActiveCamera::queue_request()] is permitted and camera configuration can no longer be changed.
-    pub fn start(&mut self, controls: Option<&ControlList>) -> io::Result<()> {
-        let ctrl_ptr = controls.map(|c| c.ptr()).unwrap_or(core::ptr::null_mut());
-        let ret = unsafe { libcamera_camera_start(self.ptr.as_ptr(), ctrl_ptr) };
+    /// Consumes the camera and returns it back in the [Stopped] state, in which [ActiveCamera::queue_request()] is no
+    /// longer permitted and camera configuration can be adjusted. On failure the camera is returned back within
+    /// [StateTransitionError].
+    pub fn stop(self) -> Result<ActiveCamera<'d, Stopped>, StateTransitionError<RunningCamera<'d>>> {
+        let ret = unsafe { libcamera_camera_stop(self.ptr.as_ptr()) };
         if ret < 0 {
-            Err(io::Error::from_raw_os_error(ret))
+            Err(StateTransitionError {
+                camera: self,
+                error: Error::from_errno("ActiveCamera::stop", -ret),
+            })
         } else {
-            Ok(())
+            Ok(self.into_state())
         }
     }
+}
 
-    /// Stops camera capture session.
+impl<'d, S> ActiveCamera<'d, S> {
+    fn into_state<T>(self) -> ActiveCamera<'d, T> {
+        ActiveCamera {
+            inner: self.inner,
+            _state: PhantomData,
+        }
+    }
+
+    /// Replaces the request completed callback and the completion queue, returning the previous ones.
+    pub(crate) fn replace_request_handlers(
+        &mut self,
+        cb: Option<RequestCompletedCallback<'d>>,
+        queue: Option<CompletionQueue>,
+    ) -> (Option<RequestCompletedCallback<'d>>, Option<CompletionQueue>) {
+        let mut state = utils::lock(&self.inner.state);
+        (
+            core::mem::replace(&mut state.request_completed_cb, cb),
+            core::mem::replace(&mut state.completion_queue, queue),
+        )
+    }
+
+    /// Sets a callback for completed camera requests.
     ///
-    /// Once stopped, [ActiveCamera::queue_request()] is no longer permitted and camera configuration can be adjusted.
-    pub fn stop(&mut self) -> io::Result<()> {
-        let ret = unsafe { libcamera_camera_stop(self.ptr.as_ptr()) };
-        if ret < 0 {
-            Err(io::Error::from_raw_os_error(ret))
-        } else {
-            Ok(())
+    /// Callback is executed in the libcamera thread context so it is best to setup a channel to send all requests for
+    /// processing elsewhere.
+    ///
+    /// Only one callback can be set at a time. If there was a previously set callback, it will be discarded when
+    /// setting a new one.
+    pub fn on_request_completed(&mut self, cb: impl FnMut(Request) + Send + 'd) {
+        let mut state = utils::lock(&self.inner.state);
+        state.request_completed_cb = Some(Box::new(cb));
+    }
+
+    /// Returns an asynchronous [Stream](futures_core::Stream) of completed requests.
+    ///
+    /// This replaces the request completed callback set by [ActiveCamera::on_request_completed()]. Up to `capacity`
+    /// completed requests are buffered until they are consumed, see [RequestStream] for the overflow behaviour.
+    ///
+    /// The stream ends once this camera is dropped, a new request completed callback is set or the completion queue
+    /// is enabled. Fails with [Error::InvalidState] if the completion queue is already enabled by
+    /// [ActiveCamera::enable_completion_queue()], as completed requests would never reach the stream.
+    #[cfg(feature = "async")]
+    pub fn requests(&mut self, capacity: usize) -> Result<RequestStream, Error> {
+        if self.inner.completion_fd.is_some() {
+            return Err(Error::InvalidState {
+                op: "ActiveCamera::requests",
+            });
+        }
+
+        let (tx, rx) = RequestStream::new(capacity);
+        self.on_request_completed(move |req| tx.send(req));
+        Ok(rx)
+    }
+
+    /// Enables a pollable queue of completed requests.
+    ///
+    /// Once enabled, the request completed callback is dropped, which also ends any stream returned by
+    /// `ActiveCamera::requests()`. Completed requests are buffered internally instead and can be retrieved with
+    /// [ActiveCamera::try_next_completed()]. The queue is backed by an `eventfd`, which is available through the
+    /// returned [CompletionQueueHandle] and becomes readable whenever there are completed requests pending. This allows
+    /// registering the camera in any epoll-based event loop.
+    ///
+    /// Calling this more than once returns a handle of the already enabled queue.
+    pub fn enable_completion_queue(&mut self) -> Result<CompletionQueueHandle, Error> {
+        if let Some(fd) = &self.inner.completion_fd {
+            return Ok(CompletionQueueHandle { fd: fd.clone() });
         }
+
+        let fd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+        if fd < 0 {
+            return Err(Error::Os {
+                op: "ActiveCamera::enable_completion_queue",
+                source: io::Error::last_os_error(),
+            });
+        }
+        let fd = Arc::new(unsafe { OwnedFd::from_raw_fd(fd) });
+
+        // The previous request completed callback is dropped once the state is unlocked.
+        let (_cb, _) = self.replace_request_handlers(
+            None,
+            Some(CompletionQueue {
+                fd: fd.clone(),
+                requests: VecDeque::new(),
+            }),
+        );
+        self.inner.completion_fd = Some(fd.clone());
+        Ok(CompletionQueueHandle { fd })
+    }
+
+    /// Returns the next completed request from the completion queue without blocking.
+    ///
+    /// Returns `None` if there are no completed requests pending or if the completion queue is not enabled by
+    /// [ActiveCamera::enable_completion_queue()].
+    pub fn try_next_completed(&self) -> Option<Request> {
+        utils::lock(&self.inner.state).completion_queue.as_mut()?.pop()
+    }
+
+    /// Sets a callback for completed stream buffers.
+    ///
+    /// libcamera completes each buffer of a multi-stream request individually, before the whole request is completed.
+    /// This callback allows consuming a buffer as soon as it is filled, without waiting for the remaining streams of
+    /// the request. The completed [Request] is still returned by the request completed callback afterwards.
+    ///
+    /// Callback is executed in the libcamera thread context, buffer data must be processed or copied before returning.
+    ///
+    /// Only one callback can be set at a time. If there was a previously set callback, it will be discarded when
+    /// setting a new one.
+    pub fn on_buffer_completed(&mut self, cb: impl FnMut(&Request, Stream, &dyn AsFrameBuffer) + Send + 'd) {
+        let mut state = utils::lock(&self.inner.state);
+        state.buffer_completed_cb = Some(Box::new(cb));
+    }
+
+    /// Sets a callback for camera disconnection (i.e. camera being unplugged).
+    ///
+    /// Requests that were queued at the time of disconnection are still owned by libcamera. Those which libcamera
+    /// still completes are returned to the request completed callback with
+    /// [RequestStatus::Cancelled](crate::request::RequestStatus::Cancelled). libcamera does not cancel the remaining
+    /// ones, not even when the camera is stopped, so they stay stranded until the camera is dropped, see
+    /// [ActiveCamera::queued_requests()]. Any following calls to [ActiveCamera::start()] or
+    /// [ActiveCamera::queue_request()] fail with [Error::Disconnected].
+    ///
+    /// Callback is executed in the libcamera thread context.
+    ///
+    /// Only one callback can be set at a time. If there was a previously set callback, it will be discarded when
+    /// setting a new one.
+    pub fn on_disconnected(&mut self, cb: impl FnMut() + Send + 'd) {
+        let mut state = utils::lock(&self.inner.state);
+        state.disconnected_cb = Some(Box::new(cb));
+    }
+
+    /// Returns `true` if the camera has been disconnected from the system.
+    pub fn is_disconnected(&self) -> bool {
+        utils::lock(&self.inner.state).disconnected
+    }
+
+    /// Returns cookies of requests, which are queued and have not been completed yet.
+    ///
+    /// Once the camera is disconnected, these are the requests libcamera will not return. They are released only when
+    /// the camera is dropped.
+    pub fn queued_requests(&self) -> Vec<u64> {
+        utils::lock(&self.inner.state)
+            .requests
+            .values()
+            .map(Request::cookie)
+            .collect()
+    }
+
+    /// Creates a capture [`Request`].
+    ///
+    /// To perform a capture, it must firstly be initialized by attaching a framebuffer with [Request::add_buffer()] and
+    /// then queued for execution by [ActiveCamera::queue_request()].
+    ///
+    /// # Arguments
+    ///
+    /// * `cookie` - An optional user-provided u64 identifier that can be used to uniquely identify request in request
+    ///   completed callback.
+    pub fn create_request(&mut self, cookie: Option<u64>) -> Option<Request> {
+        let req = unsafe { libcamera_camera_create_request(self.ptr.as_ptr(), cookie.unwrap_or(0)) };
+        NonNull::new(req).map(|p| unsafe { Request::from_ptr(p) })
     }
 }
 
-impl<'d> Deref for ActiveCamera<'d> {
+impl<'d, S> Deref for ActiveCamera<'d, S> {
     type Target = Camera<'d>;
 
     fn deref(&self) -> &Self::Target {
-        &self.cam
+        &self.inner.cam
     }
 }
 
-impl DerefMut for ActiveCamera<'_> {
+impl<S> DerefMut for ActiveCamera<'_, S> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.cam
-    }
-}
-
-impl Drop for ActiveCamera<'_> {
-    fn drop(&mut self) {
-        unsafe {
-            libcamera_camera_request_completed_disconnect(self.ptr.as_ptr(), self.request_completed_handle);
-            libcamera_camera_stop(self.ptr.as_ptr());
-            libcamera_camera_release(self.ptr.as_ptr());
-        }
+        &mut self.inner.cam
     }
 }
@@ -1,26 +1,101 @@
 use std::{
     ffi::{CStr, CString},
-    io,
     marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
     ptr::NonNull,
+    sync::Mutex,
 };
 
 use libcamera_sys::*;
 
-use crate::{camera::Camera, logging::LoggingLevel, utils::handle_result};
+use crate::{camera::Camera, control::PropertyList, error::Error, logging::LoggingLevel, utils};
+
+extern "C" fn camera_added_cb(ptr: *mut core::ffi::c_void, cam: *mut libcamera_camera_t) {
+    let mut state = utils::lock(unsafe { &*(ptr as *const Mutex<CameraManagerState>) });
+    let Some(cam) = NonNull::new(cam) else {
+        return;
+    };
+    let cam = unsafe { Camera::from_ptr(cam) };
+
+    if let Some(cb) = &mut state.camera_added_cb {
+        let res = panic::catch_unwind(AssertUnwindSafe(|| cb(cam)));
+        utils::record_panic(&mut state.callback_panic, "camera added", res);
+    }
+}
+
+extern "C" fn camera_removed_cb(ptr: *mut core::ffi::c_void, cam: *mut libcamera_camera_t) {
+    let mut state = utils::lock(unsafe { &*(ptr as *const Mutex<CameraManagerState>) });
+    let Some(cam) = NonNull::new(cam) else {
+        return;
+    };
+    let cam = unsafe { Camera::from_ptr(cam) };
+
+    if let Some(cb) = &mut state.camera_removed_cb {
+        let res = panic::catch_unwind(AssertUnwindSafe(|| cb(cam)));
+        utils::record_panic(&mut state.callback_panic, "camera removed", res);
+    }
+}
+
+type CameraCallback = Box<dyn FnMut(Camera<'_>) + Send>;
+
+#[derive(Default)]
+struct CameraManagerState {
+    /// Callback for libcamera `cameraAdded` signal.
+    camera_added_cb: Option<CameraCallback>,
+    /// Callback for libcamera `cameraRemoved` signal.
+    camera_removed_cb: Option<CameraCallback>,
+    /// Message of a panic caught in a user callback, which is yet to be reported to the application.
+    callback_panic: Option<String>,
+}
 
 /// Camera manager used to enumerate available cameras in the system.
 pub struct CameraManager {
     ptr: NonNull<libcamera_camera_manager_t>,
+    /// Handle to disconnect `cameraAdded` signal.
+    camera_added_handle: *mut libcamera_callback_handle_t,
+    /// Handle to disconnect `cameraRemoved` signal.
+    camera_removed_handle: *mut libcamera_callback_handle_t,
+    /// Internal state that is shared with callback handlers.
+    state: Box<Mutex<CameraManagerState>>,
 }
 
 impl CameraManager {
     /// Initializes `libcamera` and creates [Self].
-    pub fn new() -> io::Result<Self> {
+    pub fn new() -> Result<Self, Error> {
         let ptr = NonNull::new(unsafe { libcamera_camera_manager_create() }).unwrap();
+        let mut state = Box::new(Mutex::new(CameraManagerState::default()));
+
+        // State is valid for the lifetime of `CameraManager` and callbacks will be disconnected on drop.
+        let camera_added_handle = unsafe {
+            libcamera_camera_manager_camera_added_connect(
+                ptr.as_ptr(),
+                Some(camera_added_cb),
+                state.as_mut() as *mut Mutex<CameraManagerState> as *mut _,
+            )
+        };
+        let camera_removed_handle = unsafe {
+            libcamera_camera_manager_camera_removed_connect(
+                ptr.as_ptr(),
+                Some(camera_removed_cb),
+                state.as_mut() as *mut Mutex<CameraManagerState> as *mut _,
+            )
+        };
+
         let ret = unsafe { libcamera_camera_manager_start(ptr.as_ptr()) };
-        handle_result(ret)?;
-        Ok(CameraManager { ptr })
+        if let Err(e) = Error::check("CameraManager::new", ret) {
+            unsafe {
+                libcamera_camera_manager_camera_added_disconnect(ptr.as_ptr(), camera_added_handle);
+                libcamera_camera_manager_camera_removed_disconnect(ptr.as_ptr(), camera_removed_handle);
+            }
+            return Err(e);
+        }
+
+        Ok(CameraManager {
+            ptr,
+            camera_added_handle,
+            camera_removed_handle,
+            state,
+        })
     }
 
     /// Returns version string of the linked libcamera.
@@ -35,6 +110,80 @@ impl CameraManager {
         unsafe { CameraList::from_ptr(NonNull::new(libcamera_camera_manager_cameras(self.ptr.as_ptr())).unwrap()) }
     }
 
+    /// Returns a camera with the given [Camera::id()].
+    ///
+    /// Returns [None] if there is no such camera in the system.
+    pub fn get(&self, id: &str) -> Option<Camera<'_>> {
+        let id = CString::new(id).ok()?;
+        let cam_ptr = unsafe { libcamera_camera_manager_get_id(self.ptr.as_ptr(), id.as_ptr()) };
+        NonNull::new(cam_ptr).map(|p| unsafe { Camera::from_ptr(p) })
+    }
+
+    /// Returns the first camera whose [Camera::properties()] satisfy the given predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use libcamera::{camera_manager::CameraManager, properties};
+    ///
+    /// let mgr = CameraManager::new().unwrap();
+    /// let cam = mgr.find(|props| {
+    ///     matches!(
+    ///         props.get::<properties::Location>(),
+    ///         Ok(properties::Location::CameraFront)
+    ///     )
+    /// });
+    /// ```
+    pub fn find(&self, mut predicate: impl FnMut(&PropertyList) -> bool) -> Option<Camera<'_>> {
+        let cameras = self.cameras();
+        (0..cameras.len())
+            .filter_map(|i| cameras.get(i))
+            .find(|cam| predicate(cam.properties()))
+    }
+
+    /// Sets a callback for cameras that are plugged into the system after [CameraManager] has been started.
+    ///
+    /// Callback is executed in the libcamera thread context so it is best to setup a channel to send camera events
+    /// for processing elsewhere. The camera can be looked up again by its [Camera::id()].
+    ///
+    /// Only one callback can be set at a time. If there was a previously set callback, it will be discarded when
+    /// setting a new one.
+    ///
+    /// Panics in the callback are caught and do not propagate into libcamera, see
+    /// [CameraManager::check_callbacks()].
+    pub fn on_camera_added(&mut self, cb: impl FnMut(Camera<'_>) + Send + 'static) {
+        let mut state = utils::lock(&self.state);
+        state.camera_added_cb = Some(Box::new(cb));
+    }
+
+    /// Sets a callback for cameras that are unplugged from the system.
+    ///
+    /// Callback is executed in the libcamera thread context. Removed camera can no longer be acquired, but its
+    /// [Camera::id()] and [Camera::properties()] can still be inspected to identify it.
+    ///
+    /// Only one callback can be set at a time. If there was a previously set callback, it will be discarded when
+    /// setting a new one.
+    ///
+    /// Panics in the callback are caught and do not propagate into libcamera, see
+    /// [CameraManager::check_callbacks()].
+    pub fn on_camera_removed(&mut self, cb: impl FnMut(Camera<'_>) + Send + 'static) {
+        let mut state = utils::lock(&self.state);
+        state.camera_removed_cb = Some(Box::new(cb));
+    }
+
+    /// Reports a panic caught in the camera added or removed callback.
+    ///
+    /// Fails with [Error::CallbackPanicked] once for the first panic since the previous call.
+    pub fn check_callbacks(&self) -> Result<(), Error> {
+        match utils::lock(&self.state).callback_panic.take() {
+            Some(message) => Err(Error::CallbackPanicked {
+                op: "CameraManager::check_callbacks",
+                message,
+            }),
+            None => Ok(()),
+        }
+    }
+
     /// Set the log level.
     ///
     /// # Parameters
@@ -54,6 +203,8 @@ impl CameraManager {
 impl Drop for CameraManager {
     fn drop(&mut self) {
         unsafe {
+            libcamera_camera_manager_camera_added_disconnect(self.ptr.as_ptr(), self.camera_added_handle);
+            libcamera_camera_manager_camera_removed_disconnect(self.ptr.as_ptr(), self.camera_removed_handle);
             libcamera_camera_manager_stop(self.ptr.as_ptr());
             libcamera_camera_manager_destroy(self.ptr.as_ptr());
         }
@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    camera::Camera,
+    configuration_spec::StreamSpec,
+    control::{control_name, ControlInfo},
+    control_value::ControlValue,
+    geometry::{Size, SizeRange},
+    pixel_format::PixelFormat,
+    properties::PropertyId,
+    stream::{StreamFormatsRef, StreamRole},
+};
+
+/// Owned report of everything a camera supports, see [Camera::capabilities()].
+///
+/// Controls and properties are keyed by name, or by numeric id if the id is not known to these bindings, and are
+/// sorted so that reports of different cameras can be compared directly.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CameraCapabilities {
+    pub id: String,
+    pub properties: BTreeMap<String, ControlValue>,
+    pub controls: BTreeMap<String, ControlCapability>,
+    /// Supported stream roles. Roles which the camera can not generate configuration for are omitted.
+    pub roles: Vec<RoleCapabilities>,
+}
+
+/// Limits of a camera control, see [ControlInfo].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ControlCapability {
+    pub min: ControlValue,
+    pub max: ControlValue,
+    pub def: ControlValue,
+    /// Discrete set of supported values, empty if any value between `min` and `max` is supported.
+    pub values: Vec<ControlValue>,
+}
+
+/// Stream formats supported for a single [StreamRole].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoleCapabilities {
+    pub role: StreamRole,
+    /// Default stream configuration generated for the role.
+    pub default: StreamSpec,
+    pub formats: Vec<FormatCapabilities>,
+}
+
+/// Sizes supported for a single [PixelFormat].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FormatCapabilities {
+    pub pixel_format: PixelFormat,
+    /// Discrete sizes, empty if any size within `range` is supported.
+    pub sizes: Vec<Size>,
+    pub range: SizeRange,
+}
+
+const ROLES: [StreamRole; 4] = [
+    StreamRole::Raw,
+    StreamRole::StillCapture,
+    StreamRole::VideoRecording,
+    StreamRole::ViewFinder,
+];
+
+impl Camera<'_> {
+    /// Collects supported stream formats of every role, control limits and properties into an owned report.
+    ///
+    /// This is the information printed by `cam --info`.
+    pub fn capabilities(&self) -> CameraCapabilities {
+        let properties = self
+            .properties()
+            .into_iter()
+            .map(|(id, val)| (property_name(id), val))
+            .collect();
+
+        let controls = self
+            .controls()
+            .into_iter()
+            .map(|(id, info)| {
+                let name = control_name(id).map_or_else(|| id.to_string(), str::to_string);
+                (name, ControlCapability::from(info))
+            })
+            .collect();
+
+        let roles = ROLES
+            .iter()
+            .filter_map(|&role| {
+                let config = self.generate_configuration(&[role])?;
+                let cfg = config.get(0)?;
+                Some(RoleCapabilities {
+                    role,
+                    default: StreamSpec::from_configuration(&cfg, Some(role)),
+                    formats: format_capabilities(&cfg.formats()),
+                })
+            })
+            .collect();
+
+        CameraCapabilities {
+            id: self.id().to_string(),
+            properties,
+            controls,
+            roles,
+        }
+    }
+}
+
+impl From<&ControlInfo> for ControlCapability {
+    fn from(info: &ControlInfo) -> Self {
+        Self {
+            min: info.min(),
+            max: info.max(),
+            def: info.def(),
+            values: info.values(),
+        }
+    }
+}
+
+fn format_capabilities(formats: &StreamFormatsRef<'_>) -> Vec<FormatCapabilities> {
+    formats
+        .pixel_formats()
+        .into_iter()
+        .map(|pixel_format| FormatCapabilities {
+            pixel_format,
+            sizes: formats.sizes(pixel_format),
+            range: formats.range(pixel_format),
+        })
+        .collect()
+}
+
+fn property_name(id: u32) -> String {
+    PropertyId::try_from(id).map_or_else(|_| id.to_string(), |id| id.name())
+}
@@ -0,0 +1,260 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    sync::mpsc::{self, Receiver},
+    time::Duration,
+};
+
+use crate::{
+    camera::{ActiveCamera, CameraConfiguration, QueueRequestError, RunningCamera},
+    control::ControlList,
+    error::Error,
+    frame::Frame,
+    request::{Request, ReuseFlag},
+    request_pool::{create_requests, PoolFrameBuffer},
+    stream::StreamInfo,
+};
+
+/// Framebuffer type attached to requests of a [CaptureSession].
+///
+/// Use it to access buffers of completed requests with [Request::buffer()].
+pub type SessionFrameBuffer = PoolFrameBuffer;
+
+/// High-level capture loop, which manages buffers and request recycling.
+///
+/// A session configures the camera, allocates and memory maps [SessionFrameBuffer]s for every configured stream,
+/// creates the capture requests and keeps them queued. Completed requests are returned by iterating the session and
+/// should be given back with [CaptureSession::recycle()] once their buffers are no longer needed, so that they are
+/// queued for capture again.
+///
+/// Iteration blocks until the next request is completed and ends when there are no requests left in flight, which
+/// happens if none of the completed requests are recycled, or once the camera gets disconnected, which also wakes up a
+/// blocked iteration. Alternatively, [CaptureSession::next_frame()] returns completed requests wrapped in a [Frame],
+/// which recycles the request automatically when dropped.
+///
+/// If a dropped [Frame] fails to queue its request again, the session keeps the request and the next call receiving a
+/// request returns it within [QueueRequestError] instead, so that the queue does not silently become shallower.
+///
+/// ```no_run
+/// use libcamera::{
+///     camera_manager::CameraManager,
+///     capture_session::{CaptureSession, SessionFrameBuffer},
+///     stream::StreamRole,
+/// };
+///
+/// let mgr = CameraManager::new().unwrap();
+/// let cameras = mgr.cameras();
+/// let cam = cameras.get(0).unwrap().acquire().unwrap();
+/// let mut cfgs = cam
+///     .generate_configuration(&[StreamRole::VideoRecording])
+///     .unwrap();
+///
+/// let mut session = CaptureSession::new(cam, &mut cfgs, 4, None).unwrap();
+/// let stream = session.streams()[0].stream;
+/// for _ in 0..60 {
+///     let req = session.next().unwrap().unwrap();
+///     let fb: &SessionFrameBuffer = req.buffer(&stream).unwrap();
+///     println!("Captured {} planes", fb.data().len());
+///     session.recycle(req).unwrap();
+/// }
+/// for _ in 0..60 {
+///     let frame = session.next_frame().unwrap().unwrap();
+///     println!("Captured frame {}", frame.sequence());
+/// }
+/// session.stop().unwrap();
+/// ```
+pub struct CaptureSession<'d> {
+    cam: RunningCamera<'d>,
+    /// Streams in the order of camera configuration.
+    streams: Vec<StreamInfo>,
+    /// Completed requests returned by the request completed callback, followed by `None` once the camera is
+    /// disconnected.
+    rx: Receiver<Option<Request>>,
+    queue: SessionQueue,
+}
+
+/// Request bookkeeping of a [CaptureSession], which is shared with its [Frame]s.
+#[derive(Default)]
+pub(crate) struct SessionQueue {
+    /// Number of requests that are queued and have not been returned yet.
+    pub(crate) in_flight: Cell<usize>,
+    /// Requests which dropped frames failed to queue again, in the order of failure.
+    pub(crate) rejected: RefCell<VecDeque<QueueRequestError>>,
+}
+
+impl<'d> CaptureSession<'d> {
+    /// Configures the camera, allocates buffers, starts capture and queues the initial requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `cam` - Camera to capture from. It is released if session fails to start.
+    /// * `config` - Camera configuration to apply. It is validated first, which may adjust it, and rejected with
+    ///   [Error::InvalidConfiguration] if libcamera considers it invalid.
+    /// * `queue_depth` - Number of requests kept queued for capture. It is limited to the number of buffers allocated
+    ///   for each stream and is at least one.
+    /// * `controls` - Optional controls to apply when starting the camera.
+    pub fn new(
+        mut cam: ActiveCamera<'d>,
+        config: &mut CameraConfiguration,
+        queue_depth: usize,
+        controls: Option<&ControlList>,
+    ) -> Result<Self, Error> {
+        if config.validate().is_invalid() {
+            return Err(Error::InvalidConfiguration {
+                op: "CaptureSession::new",
+            });
+        }
+        cam.configure(config)?;
+
+        let streams = (0..config.len())
+            .map(|i| config.get(i).and_then(|cfg| cfg.info()))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(Error::InvalidConfiguration {
+                op: "CaptureSession::new",
+            })?;
+        let reqs = create_requests(
+            &mut cam,
+            &streams.iter().map(|info| info.stream).collect::<Vec<_>>(),
+            queue_depth,
+            "CaptureSession::new",
+        )?;
+
+        let (tx, rx) = mpsc::channel();
+        let disconnected_tx = tx.clone();
+        // Receiver is only gone once the session is dropped.
+        cam.on_request_completed(move |req| {
+            let _ = tx.send(Some(req));
+        });
+        // Requests of a disconnected camera may never be returned, so wake up the receiver explicitly.
+        cam.on_disconnected(move || {
+            let _ = disconnected_tx.send(None);
+        });
+
+        let cam = cam.start(controls).map_err(Error::from)?;
+
+        let session = Self {
+            cam,
+            streams,
+            rx,
+            queue: SessionQueue::default(),
+        };
+        for req in reqs {
+            session.queue(req)?;
+        }
+
+        Ok(session)
+    }
+
+    fn queue(&self, req: Request) -> Result<(), QueueRequestError> {
+        self.cam.queue_request(req)?;
+        self.queue.in_flight.set(self.queue.in_flight.get() + 1);
+        Ok(())
+    }
+
+    /// Returns request which a dropped frame failed to queue again, if any.
+    ///
+    /// Otherwise takes the next completed request if there is one in flight and the camera is connected, decrementing
+    /// the counter.
+    fn receive(&self, timeout: Option<Duration>) -> Result<Option<Request>, QueueRequestError> {
+        if let Some(e) = self.queue.rejected.borrow_mut().pop_front() {
+            return Err(e);
+        }
+        if self.queue.in_flight.get() == 0 || self.cam.is_disconnected() {
+            return Ok(None);
+        }
+
+        let req = match timeout {
+            Some(timeout) => self.rx.recv_timeout(timeout).ok().flatten(),
+            None => self.rx.recv().ok().flatten(),
+        };
+        if req.is_some() {
+            self.queue.in_flight.set(self.queue.in_flight.get() - 1);
+        }
+        Ok(req)
+    }
+
+    /// Returns the running camera.
+    pub fn camera(&self) -> &RunningCamera<'d> {
+        &self.cam
+    }
+
+    /// Returns the configured streams, in the same order as within camera configuration.
+    pub fn streams(&self) -> &[StreamInfo] {
+        &self.streams
+    }
+
+    /// Returns number of requests that are queued for capture and have not been returned yet.
+    pub fn in_flight(&self) -> usize {
+        self.queue.in_flight.get()
+    }
+
+    /// Waits for the next completed request for at most `timeout`.
+    ///
+    /// Returns `None` if the timeout has elapsed, if there are no requests in flight or if the camera is disconnected.
+    /// Fails with the request of a dropped [Frame], which could not be queued again.
+    pub fn next_timeout(&mut self, timeout: Duration) -> Result<Option<Request>, QueueRequestError> {
+        self.receive(Some(timeout))
+    }
+
+    /// Blocks until the next request is completed and returns it as a [Frame], which is recycled when dropped.
+    ///
+    /// Returns `None` if there are no requests in flight or if the camera is disconnected. Fails with the request of a
+    /// dropped [Frame], which could not be queued again.
+    pub fn next_frame(&self) -> Result<Option<Frame<'_, 'd>>, QueueRequestError> {
+        Ok(self.receive(None)?.map(|req| self.frame(req)))
+    }
+
+    /// Waits for the next completed request for at most `timeout` and returns it as a [Frame], which is recycled
+    /// when dropped.
+    ///
+    /// Returns `None` if the timeout has elapsed, if there are no requests in flight or if the camera is disconnected.
+    /// Fails with the request of a dropped [Frame], which could not be queued again.
+    pub fn next_frame_timeout(&self, timeout: Duration) -> Result<Option<Frame<'_, 'd>>, QueueRequestError> {
+        Ok(self.receive(Some(timeout))?.map(|req| self.frame(req)))
+    }
+
+    fn frame(&self, req: Request) -> Frame<'_, 'd> {
+        Frame::new(&self.cam, req, &self.streams).with_session(&self.queue)
+    }
+
+    /// Queues completed request for capture again, reusing its buffers.
+    ///
+    /// If the request is rejected, it is returned back within [QueueRequestError], so that it can be recycled again.
+    pub fn recycle(&self, mut req: Request) -> Result<(), QueueRequestError> {
+        req.reuse(ReuseFlag::REUSE_BUFFERS);
+        self.queue(req)
+    }
+
+    /// Stops the capture and returns the camera, so that it can be reconfigured.
+    ///
+    /// libcamera cancels all outstanding requests when stopping, which are then discarded together with all session
+    /// buffers. The camera is released if stopping fails.
+    pub fn stop(self) -> Result<ActiveCamera<'d>, Error> {
+        let cam = self.cam.stop()?;
+        // Drain requests that were completed or cancelled while stopping.
+        self.rx.try_iter().for_each(drop);
+        Ok(cam)
+    }
+}
+
+impl Iterator for CaptureSession<'_> {
+    type Item = Result<Request, QueueRequestError>;
+
+    /// Blocks until the next request is completed.
+    ///
+    /// Returns `None` if there are no requests in flight or if the camera is disconnected. Yields an error with the
+    /// request of a dropped [Frame], which could not be queued again.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receive(None).transpose()
+    }
+}
+
+impl core::fmt::Debug for CaptureSession<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CaptureSession")
+            .field("camera", &self.cam.id())
+            .field("streams", &self.streams.len())
+            .field("in_flight", &self.queue.in_flight.get())
+            .finish()
+    }
+}
@@ -0,0 +1,287 @@
+use std::{fmt, str::FromStr};
+
+use libcamera_sys::*;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use thiserror::Error;
+
+/// Color primaries of a [ColorSpace].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Primaries {
+    /// Unprocessed sensor data.
+    Raw = libcamera_color_space_primaries::LIBCAMERA_COLOR_SPACE_PRIMARIES_RAW,
+    Smpte170m = libcamera_color_space_primaries::LIBCAMERA_COLOR_SPACE_PRIMARIES_SMPTE170M,
+    Rec709 = libcamera_color_space_primaries::LIBCAMERA_COLOR_SPACE_PRIMARIES_REC709,
+    Rec2020 = libcamera_color_space_primaries::LIBCAMERA_COLOR_SPACE_PRIMARIES_REC2020,
+}
+
+/// Transfer function of a [ColorSpace].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum TransferFunction {
+    Linear = libcamera_color_space_transfer_function::LIBCAMERA_COLOR_SPACE_TRANSFER_FUNCTION_LINEAR,
+    Srgb = libcamera_color_space_transfer_function::LIBCAMERA_COLOR_SPACE_TRANSFER_FUNCTION_SRGB,
+    Rec709 = libcamera_color_space_transfer_function::LIBCAMERA_COLOR_SPACE_TRANSFER_FUNCTION_REC709,
+}
+
+/// Y'CbCr encoding of a [ColorSpace].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum YcbcrEncoding {
+    /// No Y'CbCr encoding, used for RGB and raw formats.
+    None = libcamera_color_space_ycbcr_encoding::LIBCAMERA_COLOR_SPACE_YCBCR_ENCODING_NONE,
+    Rec601 = libcamera_color_space_ycbcr_encoding::LIBCAMERA_COLOR_SPACE_YCBCR_ENCODING_REC601,
+    Rec709 = libcamera_color_space_ycbcr_encoding::LIBCAMERA_COLOR_SPACE_YCBCR_ENCODING_REC709,
+    Rec2020 = libcamera_color_space_ycbcr_encoding::LIBCAMERA_COLOR_SPACE_YCBCR_ENCODING_REC2020,
+}
+
+/// Quantization range of a [ColorSpace].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Range {
+    /// Full range, e.g. 0-255 for 8-bit samples.
+    Full = libcamera_color_space_range::LIBCAMERA_COLOR_SPACE_RANGE_FULL,
+    /// Limited range, e.g. 16-235 for 8-bit luma samples.
+    Limited = libcamera_color_space_range::LIBCAMERA_COLOR_SPACE_RANGE_LIMITED,
+}
+
+/// Color space of image data, see
+/// [StreamConfigurationRef::get_color_space()](crate::stream::StreamConfigurationRef::get_color_space).
+///
+/// String representation matches libcamera: one of the preset names (e.g. `sYCC`, `Rec709`) or all four components
+/// separated by slashes (e.g. `Rec709/sRGB/Rec601/Limited`).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct ColorSpace {
+    pub primaries: Primaries,
+    pub transfer_function: TransferFunction,
+    pub ycbcr_encoding: YcbcrEncoding,
+    pub range: Range,
+}
+
+impl ColorSpace {
+    /// Unprocessed sensor data.
+    pub const RAW: Self = Self::new(
+        Primaries::Raw,
+        TransferFunction::Linear,
+        YcbcrEncoding::None,
+        Range::Full,
+    );
+    /// sRGB, for RGB formats.
+    pub const SRGB: Self = Self::new(
+        Primaries::Rec709,
+        TransferFunction::Srgb,
+        YcbcrEncoding::None,
+        Range::Full,
+    );
+    /// sYCC, the full range Y'CbCr encoding of sRGB used by JPEG.
+    pub const SYCC: Self = Self::new(
+        Primaries::Rec709,
+        TransferFunction::Srgb,
+        YcbcrEncoding::Rec601,
+        Range::Full,
+    );
+    /// SMPTE 170M, used by standard definition video.
+    pub const SMPTE170M: Self = Self::new(
+        Primaries::Smpte170m,
+        TransferFunction::Rec709,
+        YcbcrEncoding::Rec601,
+        Range::Limited,
+    );
+    /// Rec. 709, used by high definition video.
+    pub const REC709: Self = Self::new(
+        Primaries::Rec709,
+        TransferFunction::Rec709,
+        YcbcrEncoding::Rec709,
+        Range::Limited,
+    );
+    /// Rec. 2020, used by ultra high definition video.
+    pub const REC2020: Self = Self::new(
+        Primaries::Rec2020,
+        TransferFunction::Rec709,
+        YcbcrEncoding::Rec2020,
+        Range::Limited,
+    );
+
+    const PRESETS: [(Self, &'static str); 6] = [
+        (Self::RAW, "RAW"),
+        (Self::SRGB, "sRGB"),
+        (Self::SYCC, "sYCC"),
+        (Self::SMPTE170M, "SMPTE170M"),
+        (Self::REC709, "Rec709"),
+        (Self::REC2020, "Rec2020"),
+    ];
+
+    pub const fn new(
+        primaries: Primaries,
+        transfer_function: TransferFunction,
+        ycbcr_encoding: YcbcrEncoding,
+        range: Range,
+    ) -> Self {
+        Self {
+            primaries,
+            transfer_function,
+            ycbcr_encoding,
+            range,
+        }
+    }
+}
+
+impl TryFrom<libcamera_color_space_t> for ColorSpace {
+    type Error = ();
+
+    fn try_from(value: libcamera_color_space_t) -> Result<Self, Self::Error> {
+        Ok(Self {
+            primaries: value.primaries.try_into().map_err(|_| ())?,
+            transfer_function: value.transfer_function.try_into().map_err(|_| ())?,
+            ycbcr_encoding: value.ycbcr_encoding.try_into().map_err(|_| ())?,
+            range: value.range.try_into().map_err(|_| ())?,
+        })
+    }
+}
+
+impl From<ColorSpace> for libcamera_color_space_t {
+    fn from(value: ColorSpace) -> Self {
+        Self {
+            primaries: value.primaries.into(),
+            transfer_function: value.transfer_function.into(),
+            ycbcr_encoding: value.ycbcr_encoding.into(),
+            range: value.range.into(),
+        }
+    }
+}
+
+const PRIMARIES_NAMES: [(Primaries, &str); 4] = [
+    (Primaries::Raw, "RAW"),
+    (Primaries::Smpte170m, "SMPTE170M"),
+    (Primaries::Rec709, "Rec709"),
+    (Primaries::Rec2020, "Rec2020"),
+];
+
+const TRANSFER_FUNCTION_NAMES: [(TransferFunction, &str); 3] = [
+    (TransferFunction::Linear, "Linear"),
+    (TransferFunction::Srgb, "sRGB"),
+    (TransferFunction::Rec709, "Rec709"),
+];
+
+const YCBCR_ENCODING_NAMES: [(YcbcrEncoding, &str); 4] = [
+    (YcbcrEncoding::None, "None"),
+    (YcbcrEncoding::Rec601, "Rec601"),
+    (YcbcrEncoding::Rec709, "Rec709"),
+    (YcbcrEncoding::Rec2020, "Rec2020"),
+];
+
+const RANGE_NAMES: [(Range, &str); 2] = [(Range::Full, "Full"), (Range::Limited, "Limited")];
+
+fn name_of<T: PartialEq>(names: &[(T, &'static str)], value: T) -> &'static str {
+    names.iter().find(|(v, _)| *v == value).unwrap().1
+}
+
+fn parse_name<T: Copy>(names: &[(T, &str)], name: &str) -> Option<T> {
+    names.iter().find(|(_, n)| *n == name).map(|(v, _)| *v)
+}
+
+impl fmt::Display for ColorSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((_, name)) = Self::PRESETS.iter().find(|(preset, _)| preset == self) {
+            return f.write_str(name);
+        }
+
+        write!(
+            f,
+            "{}/{}/{}/{}",
+            name_of(&PRIMARIES_NAMES, self.primaries),
+            name_of(&TRANSFER_FUNCTION_NAMES, self.transfer_function),
+            name_of(&YCBCR_ENCODING_NAMES, self.ycbcr_encoding),
+            name_of(&RANGE_NAMES, self.range),
+        )
+    }
+}
+
+/// Error returned when parsing a [ColorSpace] from string fails.
+#[derive(Debug, Clone, Error)]
+#[error("invalid color space: {0:?}")]
+pub struct ParseColorSpaceError(String);
+
+impl FromStr for ColorSpace {
+    type Err = ParseColorSpaceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((preset, _)) = Self::PRESETS.iter().find(|(_, name)| *name == s) {
+            return Ok(*preset);
+        }
+
+        let err = || ParseColorSpaceError(s.to_string());
+        let mut parts = s.split('/');
+        let mut next = || parts.next().ok_or_else(err);
+        let color_space = Self {
+            primaries: parse_name(&PRIMARIES_NAMES, next()?).ok_or_else(err)?,
+            transfer_function: parse_name(&TRANSFER_FUNCTION_NAMES, next()?).ok_or_else(err)?,
+            ycbcr_encoding: parse_name(&YCBCR_ENCODING_NAMES, next()?).ok_or_else(err)?,
+            range: parse_name(&RANGE_NAMES, next()?).ok_or_else(err)?,
+        };
+
+        match parts.next() {
+            Some(_) => Err(err()),
+            None => Ok(color_space),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ColorSpace {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ColorSpace {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_round_trip() {
+        for (preset, name) in ColorSpace::PRESETS {
+            assert_eq!(preset.to_string(), name);
+            assert_eq!(name.parse::<ColorSpace>().unwrap(), preset);
+        }
+    }
+
+    #[test]
+    fn components_round_trip() {
+        for (primaries, _) in PRIMARIES_NAMES {
+            for (transfer_function, _) in TRANSFER_FUNCTION_NAMES {
+                for (ycbcr_encoding, _) in YCBCR_ENCODING_NAMES {
+                    for (range, _) in RANGE_NAMES {
+                        let color_space = ColorSpace::new(primaries, transfer_function, ycbcr_encoding, range);
+                        assert_eq!(color_space.to_string().parse::<ColorSpace>().unwrap(), color_space);
+                    }
+                }
+            }
+        }
+        assert_eq!(
+            "Rec709/sRGB/Rec601/Limited".parse::<ColorSpace>().unwrap().to_string(),
+            "Rec709/sRGB/Rec601/Limited"
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for s in [
+            "",
+            "rec709",
+            "Rec709/sRGB/Rec601",
+            "Rec709/sRGB/Rec601/Limited/Full",
+            "Rec709/sRGB/Rec601/Narrow",
+        ] {
+            assert!(s.parse::<ColorSpace>().is_err(), "{s:?}");
+        }
+    }
+}
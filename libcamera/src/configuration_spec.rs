@@ -0,0 +1,254 @@
+use crate::{
+    camera::{Camera, CameraConfiguration, SensorConfiguration, SensorSkipping},
+    color_space::ColorSpace,
+    error::Error,
+    geometry::{Rectangle, Size},
+    orientation::Orientation,
+    pixel_format::PixelFormat,
+    stream::{StreamConfigurationRef, StreamRole},
+};
+
+/// Owned snapshot of a [CameraConfiguration], which can be stored and applied again later.
+///
+/// With the `serde` feature enabled the spec can be (de)serialized, with pixel formats and color spaces represented by
+/// their libcamera names.
+///
+/// ```no_run
+/// use libcamera::{
+///     camera_manager::CameraManager, configuration_spec::CameraConfigurationSpec,
+///     stream::StreamRole,
+/// };
+///
+/// let mgr = CameraManager::new().unwrap();
+/// let cameras = mgr.cameras();
+/// let cam = cameras.get(0).unwrap();
+///
+/// let roles = [StreamRole::VideoRecording];
+/// let mut cfgs = cam.generate_configuration(&roles).unwrap();
+/// cfgs.validate();
+/// let spec = CameraConfigurationSpec::from_configuration(&cfgs, &roles);
+///
+/// // Later on, possibly after loading the spec from a config file
+/// let mut cfgs = spec.generate_configuration(&cam).unwrap();
+/// cfgs.validate();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CameraConfigurationSpec {
+    pub streams: Vec<StreamSpec>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sensor_configuration: Option<SensorConfigurationSpec>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub orientation: Orientation,
+}
+
+/// Owned snapshot of a [StreamConfigurationRef].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamSpec {
+    /// Role used to generate the stream configuration, or `None` if the stream was added explicitly.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub role: Option<StreamRole>,
+    pub pixel_format: PixelFormat,
+    pub size: Size,
+    pub buffer_count: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub color_space: Option<ColorSpace>,
+}
+
+/// Owned snapshot of a [SensorConfiguration].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SensorConfigurationSpec {
+    pub bit_depth: u32,
+    pub analog_crop: Rectangle,
+    pub binning: (u32, u32),
+    pub skipping: SensorSkipping,
+    pub output_size: Size,
+}
+
+impl CameraConfigurationSpec {
+    /// Captures the current state of `config`, which is typically validated beforehand.
+    ///
+    /// `roles` are the stream roles that `config` was generated for. Streams without a matching role, e.g. added with
+    /// [CameraConfiguration::add_stream()], are recorded without one.
+    pub fn from_configuration(config: &CameraConfiguration, roles: &[StreamRole]) -> Self {
+        let streams = (0..config.len())
+            .filter_map(|i| config.get(i))
+            .enumerate()
+            .map(|(i, cfg)| StreamSpec::from_configuration(&cfg, roles.get(i).copied()))
+            .collect();
+
+        Self {
+            streams,
+            sensor_configuration: config
+                .sensor_configuration()
+                .as_ref()
+                .map(SensorConfigurationSpec::from),
+            orientation: config.orientation(),
+        }
+    }
+
+    /// Returns roles of the leading streams that have one, which can be passed to
+    /// [Camera::generate_configuration()].
+    pub fn roles(&self) -> Vec<StreamRole> {
+        self.streams.iter().map_while(|s| s.role).collect()
+    }
+
+    /// Generates camera configuration for the spec roles and applies the spec onto it.
+    ///
+    /// The resulting configuration still has to be validated.
+    pub fn generate_configuration(&self, cam: &Camera<'_>) -> Option<CameraConfiguration> {
+        let mut config = cam.generate_configuration(&self.roles())?;
+        self.apply(cam, &mut config).ok()?;
+        Some(config)
+    }
+
+    /// Applies the spec onto `config`, typically generated by [Camera::generate_configuration()] for
+    /// [CameraConfigurationSpec::roles()].
+    ///
+    /// Streams are matched by index. Missing streams are added and the extra ones are removed, so that `config`
+    /// contains exactly the streams of the spec. Added streams are seeded from the defaults of their role, or of
+    /// [StreamRole::ViewFinder] if the spec does not record one, see [CameraConfiguration::add_stream()]. Sensor
+    /// configuration of `config` is cleared if the spec has none.
+    ///
+    /// Fails with [Error::InvalidConfiguration] if a stream can not be added or removed, leaving `config` partially
+    /// updated.
+    pub fn apply(&self, cam: &Camera<'_>, config: &mut CameraConfiguration) -> Result<(), Error> {
+        const OP: &str = "CameraConfigurationSpec::apply";
+
+        while config.len() > self.streams.len() {
+            if !config.remove_stream(cam, config.len() - 1) {
+                return Err(Error::InvalidConfiguration { op: OP });
+            }
+        }
+
+        for (i, stream) in self.streams.iter().enumerate() {
+            let mut cfg = match config.get_mut(i) {
+                Some(cfg) => cfg,
+                None => config
+                    .add_stream(cam, stream.role.unwrap_or(StreamRole::ViewFinder))
+                    .ok_or(Error::InvalidConfiguration { op: OP })?,
+            };
+            stream.apply(&mut cfg);
+        }
+
+        match &self.sensor_configuration {
+            Some(sensor) => config.set_sensor_configuration(sensor.into()),
+            None => config.clear_sensor_configuration(),
+        }
+        config.set_orientation(self.orientation);
+        Ok(())
+    }
+}
+
+impl StreamSpec {
+    /// Captures the current state of `cfg`, generated for `role`.
+    pub fn from_configuration(cfg: &StreamConfigurationRef<'_>, role: Option<StreamRole>) -> Self {
+        Self {
+            role,
+            pixel_format: cfg.get_pixel_format(),
+            size: cfg.get_size(),
+            buffer_count: cfg.get_buffer_count(),
+            color_space: cfg.get_color_space(),
+        }
+    }
+
+    /// Applies the spec onto `cfg`.
+    pub fn apply(&self, cfg: &mut StreamConfigurationRef<'_>) {
+        cfg.set_pixel_format(self.pixel_format);
+        cfg.set_size(self.size);
+        cfg.set_buffer_count(self.buffer_count);
+        cfg.set_color_space(self.color_space);
+    }
+}
+
+impl From<&SensorConfiguration> for SensorConfigurationSpec {
+    fn from(sensor: &SensorConfiguration) -> Self {
+        Self {
+            bit_depth: sensor.bit_depth(),
+            analog_crop: sensor.analog_crop(),
+            binning: sensor.binning(),
+            skipping: sensor.skipping(),
+            output_size: sensor.output_size(),
+        }
+    }
+}
+
+impl From<&SensorConfigurationSpec> for SensorConfiguration {
+    fn from(spec: &SensorConfigurationSpec) -> Self {
+        let mut sensor = SensorConfiguration::new();
+        sensor.set_bit_depth(spec.bit_depth);
+        sensor.set_analog_crop(spec.analog_crop);
+        sensor.set_binning(spec.binning.0, spec.binning.1);
+        sensor.set_skipping(spec.skipping);
+        sensor.set_output_size(spec.output_size.width, spec.output_size.height);
+        sensor
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    fn stream(role: Option<StreamRole>, fourcc: &[u8; 4]) -> StreamSpec {
+        StreamSpec {
+            role,
+            pixel_format: PixelFormat::new(u32::from_le_bytes(*fourcc), 0),
+            size: Size {
+                width: 1920,
+                height: 1080,
+            },
+            buffer_count: 4,
+            color_space: Some(ColorSpace::REC709),
+        }
+    }
+
+    #[test]
+    fn spec_round_trip() {
+        let spec = CameraConfigurationSpec {
+            streams: vec![stream(Some(StreamRole::VideoRecording), b"YU12"), stream(None, b"MJPG")],
+            sensor_configuration: Some(SensorConfigurationSpec {
+                bit_depth: 10,
+                analog_crop: Rectangle {
+                    x: 0,
+                    y: 8,
+                    width: 4056,
+                    height: 3040,
+                },
+                binning: (2, 2),
+                skipping: SensorSkipping::default(),
+                output_size: Size {
+                    width: 2028,
+                    height: 1520,
+                },
+            }),
+            orientation: Orientation::Rotate180,
+        };
+
+        let json = serde_json::to_string(&spec).unwrap();
+        assert_eq!(serde_json::from_str::<CameraConfigurationSpec>(&json).unwrap(), spec);
+    }
+
+    #[test]
+    fn stream_round_trip() {
+        let spec = StreamSpec {
+            color_space: None,
+            ..stream(Some(StreamRole::StillCapture), b"NV12")
+        };
+
+        let json = serde_json::to_string(&spec).unwrap();
+        assert_eq!(serde_json::from_str::<StreamSpec>(&json).unwrap(), spec);
+    }
+
+    #[test]
+    fn optional_fields_default() {
+        let json = r#"{"streams":[{"pixel_format":"NV12","size":{"width":640,"height":480},"buffer_count":2}]}"#;
+        let spec = serde_json::from_str::<CameraConfigurationSpec>(json).unwrap();
+
+        assert_eq!(spec.streams[0].role, None);
+        assert_eq!(spec.streams[0].color_space, None);
+        assert_eq!(spec.sensor_configuration, None);
+        assert_eq!(spec.orientation, Orientation::Rotate0);
+    }
+}
@@ -520,12 +520,22 @@ pub enum ControlDirection {
     InOut = LIBCAMERA_CONTROL_DIRECTION_IN | LIBCAMERA_CONTROL_DIRECTION_OUT,
 }
 
+/// Returns name of control `id`, including controls unknown to the generated [ControlId] of this libcamera version.
+///
+/// Draft controls are the only ones matched by name: depending on the libcamera version the bindings were generated
+/// for, they are part of `draft`, promoted to core controls or missing, so their generated types can not be relied on.
+/// Use [ControlList::get()] with the generated types for all other controls.
+pub(crate) fn control_name(id: u32) -> Option<&'static str> {
+    let name = unsafe { libcamera_control_name_from_id(id) };
+    if name.is_null() {
+        return None;
+    }
+    unsafe { CStr::from_ptr(name) }.to_str().ok()
+}
+
 impl ControlId {
     pub fn name(&self) -> String {
-        unsafe { CStr::from_ptr(libcamera_control_name_from_id(self.id())) }
-            .to_str()
-            .unwrap()
-            .into()
+        control_name(self.id()).unwrap().into()
     }
 
     fn as_ptr(&self) -> *mut libcamera_control_id_t {
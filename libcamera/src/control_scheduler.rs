@@ -0,0 +1,378 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::{
+    camera::{QueueRequestError, RunningCamera},
+    control::Control,
+    control_value::ControlValue,
+    request::{Request, RequestStatus},
+};
+
+/// Identifier of a control change, returned by [ControlScheduler::schedule()].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScheduleId(u64);
+
+/// Outcome of a scheduled control change, returned by [ControlScheduler::poll()].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScheduleEvent {
+    /// Request metadata confirmed that the change took effect.
+    Applied {
+        id: ScheduleId,
+        /// Frame the change was scheduled for.
+        target: u64,
+        /// First frame which reported the requested value.
+        frame: u64,
+        /// Sequence number of that frame, see [Request::sequence()].
+        sequence: u32,
+    },
+    /// None of the frames within the confirmation window reported the requested value.
+    ///
+    /// This is also reported for controls which are not returned in request metadata.
+    Unconfirmed { id: ScheduleId, target: u64 },
+    /// The control is not supported by the camera, so it was not attached to a request.
+    Rejected { id: ScheduleId, target: u64 },
+}
+
+#[derive(Debug, Clone)]
+struct Change {
+    id: ScheduleId,
+    target: u64,
+    /// Frame of the request the change is attached to, set once the request is queued.
+    frame: u64,
+    control: u32,
+    value: ControlValue,
+}
+
+/// Applies control changes on specific frames.
+///
+/// Frames are numbered by the order in which requests are queued with [ControlScheduler::queue_request()], starting
+/// from zero, see [ControlScheduler::next_frame()]. Completed requests must be passed to
+/// [ControlScheduler::complete()] in the order of completion, which libcamera guarantees to be the queueing order.
+///
+/// Pipelines apply request controls with a delay of a few frames. With latency set to `n`, a change scheduled for
+/// frame `N` is attached to the request of frame `N - n`. Changes scheduled too late are attached to the next queued
+/// request. The scheduler then compares request metadata against the requested values to report which frame the
+/// change actually took effect on, so that the latency can be calibrated.
+///
+/// ```no_run
+/// use std::sync::mpsc::Receiver;
+///
+/// use libcamera::{
+///     camera::RunningCamera,
+///     control_scheduler::{ControlScheduler, ScheduleEvent},
+///     controls::ExposureTime,
+///     request::{Request, ReuseFlag},
+/// };
+///
+/// # fn run(cam: &RunningCamera<'_>, reqs: Vec<Request>, rx: Receiver<Request>) {
+/// let mut scheduler = ControlScheduler::new(2);
+/// // Exposure bracketing on three consecutive frames
+/// for (i, exposure) in [5000, 10000, 20000].into_iter().enumerate() {
+///     scheduler.schedule_control(10 + i as u64, ExposureTime(exposure));
+/// }
+///
+/// for req in reqs {
+///     scheduler.queue_request(cam, req).unwrap();
+/// }
+/// for mut req in rx {
+///     scheduler.complete(&req);
+///     while let Some(event) = scheduler.poll() {
+///         if let ScheduleEvent::Applied { target, frame, .. } = event {
+///             println!("Scheduled for frame {target}, applied on frame {frame}");
+///         }
+///     }
+///     req.reuse(ReuseFlag::REUSE_BUFFERS);
+///     scheduler.queue_request(cam, req).unwrap();
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ControlScheduler {
+    latency: u32,
+    /// Number of frames after the target frame to wait for confirmation.
+    confirm_window: u64,
+    /// Relative tolerance of numeric values reported in metadata.
+    tolerance: f64,
+    next_id: u64,
+    /// Frame index of the next queued request.
+    next_frame: u64,
+    /// Frame index of the next completed request.
+    next_completed: u64,
+    /// Changes not yet attached to a request, keyed by the frame index of the request to attach them to.
+    pending: BTreeMap<u64, Vec<Change>>,
+    /// Changes attached to queued requests, waiting for confirmation.
+    attached: Vec<Change>,
+    events: VecDeque<ScheduleEvent>,
+}
+
+impl ControlScheduler {
+    /// Creates a scheduler for a pipeline applying controls `latency` frames after the request they are attached to.
+    pub fn new(latency: u32) -> Self {
+        Self {
+            latency,
+            confirm_window: latency as u64 + 4,
+            tolerance: 0.01,
+            next_id: 0,
+            next_frame: 0,
+            next_completed: 0,
+            pending: BTreeMap::new(),
+            attached: Vec::new(),
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Sets the number of frames after the target frame to wait for confirmation in metadata, which defaults to
+    /// latency + 4.
+    pub fn set_confirm_window(&mut self, frames: u64) {
+        self.confirm_window = frames;
+    }
+
+    /// Sets relative tolerance used when comparing numeric metadata with the requested value, which defaults to 1%.
+    ///
+    /// Sensors quantize values such as exposure time, so metadata rarely reports the exact requested value.
+    pub fn set_tolerance(&mut self, tolerance: f64) {
+        self.tolerance = tolerance;
+    }
+
+    /// Returns index of the frame that will be captured by the next request queued with
+    /// [ControlScheduler::queue_request()].
+    pub fn next_frame(&self) -> u64 {
+        self.next_frame
+    }
+
+    /// Schedules raw control `id` to take `value` on `frame`.
+    pub fn schedule(&mut self, frame: u64, id: u32, value: ControlValue) -> ScheduleId {
+        let change_id = ScheduleId(self.next_id);
+        self.next_id += 1;
+
+        let attach_at = frame.saturating_sub(self.latency as u64).max(self.next_frame);
+        self.pending.entry(attach_at).or_default().push(Change {
+            id: change_id,
+            target: frame,
+            frame: attach_at,
+            control: id,
+            value,
+        });
+        change_id
+    }
+
+    /// Schedules `control` to take effect on `frame`.
+    pub fn schedule_control<C: Control>(&mut self, frame: u64, control: C) -> ScheduleId {
+        self.schedule(frame, C::ID, control.into())
+    }
+
+    /// Attaches changes due for the next frame to `req` and queues it.
+    ///
+    /// Changes of controls missing from [Camera::controls()](crate::camera::Camera::controls) are reported as
+    /// [ScheduleEvent::Rejected]. If the request is rejected, the changes are attached to the next queued request
+    /// instead.
+    pub fn queue_request(&mut self, cam: &RunningCamera<'_>, mut req: Request) -> Result<(), QueueRequestError> {
+        let mut changes = Vec::new();
+        for change in self.take_due() {
+            if cam.controls().find(change.control).is_ok()
+                && req.controls_mut().set_raw(change.control, change.value.clone()).is_ok()
+            {
+                changes.push(change);
+            } else {
+                self.events.push_back(ScheduleEvent::Rejected {
+                    id: change.id,
+                    target: change.target,
+                });
+            }
+        }
+
+        match cam.queue_request(req) {
+            Ok(()) => {
+                self.attach(changes);
+                Ok(())
+            }
+            Err(e) => {
+                if !changes.is_empty() {
+                    self.pending.entry(self.next_frame).or_default().extend(changes);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Removes changes due for the next queued request from `pending`.
+    fn take_due(&mut self) -> Vec<Change> {
+        let later = self.pending.split_off(&(self.next_frame + 1));
+        std::mem::replace(&mut self.pending, later)
+            .into_values()
+            .flatten()
+            .collect()
+    }
+
+    /// Records `changes` as attached to the request of the next frame, which has just been queued.
+    fn attach(&mut self, changes: Vec<Change>) {
+        let frame = self.next_frame;
+        self.next_frame += 1;
+        self.attached
+            .extend(changes.into_iter().map(|change| Change { frame, ..change }));
+    }
+
+    /// Checks metadata of a completed request for scheduled changes.
+    ///
+    /// Must be called for every request queued with [ControlScheduler::queue_request()], in the order of completion.
+    pub fn complete(&mut self, req: &Request) {
+        let metadata = if req.status() == RequestStatus::Complete {
+            req.metadata().into_iter().collect()
+        } else {
+            Vec::new()
+        };
+        self.complete_frame(&metadata, req.sequence());
+    }
+
+    /// Checks `metadata` of the next completed frame, captured with `sequence`, for attached changes.
+    fn complete_frame(&mut self, metadata: &[(u32, ControlValue)], sequence: u32) {
+        let frame = self.next_completed;
+        self.next_completed += 1;
+
+        let mut remaining = Vec::with_capacity(self.attached.len());
+        for change in self.attached.drain(..) {
+            // Requests queued before the change was attached may still report the requested value
+            if frame < change.frame {
+                remaining.push(change);
+                continue;
+            }
+
+            let reported = metadata
+                .iter()
+                .find(|(id, _)| *id == change.control)
+                .is_some_and(|(_, val)| values_match(val, &change.value, self.tolerance));
+
+            if reported {
+                self.events.push_back(ScheduleEvent::Applied {
+                    id: change.id,
+                    target: change.target,
+                    frame,
+                    sequence,
+                });
+            } else if frame >= change.target.saturating_add(self.confirm_window) {
+                self.events.push_back(ScheduleEvent::Unconfirmed {
+                    id: change.id,
+                    target: change.target,
+                });
+            } else {
+                remaining.push(change);
+            }
+        }
+        self.attached = remaining;
+    }
+
+    /// Returns the next event, or `None` if more requests have to be completed first.
+    pub fn poll(&mut self) -> Option<ScheduleEvent> {
+        self.events.pop_front()
+    }
+
+    /// Discards all scheduled changes and restarts frame numbering, e.g. after the camera is restarted.
+    pub fn reset(&mut self) {
+        self.next_frame = 0;
+        self.next_completed = 0;
+        self.pending.clear();
+        self.attached.clear();
+        self.events.clear();
+    }
+}
+
+/// Compares reported metadata value with the requested one, allowing relative `tolerance` for numeric values.
+fn values_match(reported: &ControlValue, requested: &ControlValue, tolerance: f64) -> bool {
+    fn numeric(val: &ControlValue) -> Option<Vec<f64>> {
+        Some(match val {
+            ControlValue::Byte(v) => v.iter().map(|&x| x as f64).collect(),
+            ControlValue::Uint16(v) => v.iter().map(|&x| x as f64).collect(),
+            ControlValue::Uint32(v) => v.iter().map(|&x| x as f64).collect(),
+            ControlValue::Int32(v) => v.iter().map(|&x| x as f64).collect(),
+            ControlValue::Int64(v) => v.iter().map(|&x| x as f64).collect(),
+            ControlValue::Float(v) => v.iter().map(|&x| x as f64).collect(),
+            _ => return None,
+        })
+    }
+
+    match (numeric(reported), numeric(requested)) {
+        (Some(a), Some(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(&b)
+                    .all(|(a, b)| (a - b).abs() <= tolerance * b.abs().max(f64::EPSILON))
+        }
+        _ => reported == requested,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPOSURE: u32 = 1;
+
+    /// Simulates queueing a request for the next frame.
+    fn queue(scheduler: &mut ControlScheduler) {
+        let changes = scheduler.take_due();
+        scheduler.attach(changes);
+    }
+
+    fn events(scheduler: &mut ControlScheduler) -> Vec<ScheduleEvent> {
+        std::iter::from_fn(|| scheduler.poll()).collect()
+    }
+
+    #[test]
+    fn attaches_with_latency() {
+        let mut scheduler = ControlScheduler::new(2);
+        scheduler.schedule(5, EXPOSURE, 10000i32.into());
+        for _ in 0..3 {
+            assert!(scheduler.take_due().is_empty());
+            scheduler.attach(Vec::new());
+        }
+        let due = scheduler.take_due();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].target, 5);
+    }
+
+    #[test]
+    fn ignores_frames_queued_before_attach() {
+        let mut scheduler = ControlScheduler::new(0);
+        queue(&mut scheduler);
+        queue(&mut scheduler);
+        let id = scheduler.schedule(2, EXPOSURE, 10000i32.into());
+        queue(&mut scheduler);
+
+        // Frames queued earlier already report the requested value
+        let metadata = [(EXPOSURE, ControlValue::from(10000i32))];
+        scheduler.complete_frame(&metadata, 100);
+        scheduler.complete_frame(&metadata, 101);
+        assert!(events(&mut scheduler).is_empty());
+
+        scheduler.complete_frame(&metadata, 102);
+        assert_eq!(
+            events(&mut scheduler),
+            [ScheduleEvent::Applied {
+                id,
+                target: 2,
+                frame: 2,
+                sequence: 102
+            }]
+        );
+    }
+
+    #[test]
+    fn unconfirmed_after_window() {
+        let mut scheduler = ControlScheduler::new(1);
+        scheduler.set_confirm_window(2);
+        let id = scheduler.schedule(1, EXPOSURE, 10000i32.into());
+        for frame in 0..4 {
+            queue(&mut scheduler);
+            scheduler.complete_frame(&[(EXPOSURE, ControlValue::from(5000i32))], frame);
+        }
+        assert_eq!(events(&mut scheduler), [ScheduleEvent::Unconfirmed { id, target: 1 }]);
+    }
+
+    #[test]
+    fn values_match_tolerance() {
+        assert!(values_match(&10050i32.into(), &10000i32.into(), 0.01));
+        assert!(!values_match(&10200i32.into(), &10000i32.into(), 0.01));
+        assert!(values_match(&ControlValue::from(1.0f32), &1.005f32.into(), 0.01));
+        assert!(!values_match(&ControlValue::from([1i32, 2]), &[1i32].into(), 0.01));
+        assert!(values_match(&true.into(), &true.into(), 0.01));
+    }
+}
@@ -23,7 +23,8 @@ pub enum ControlValueError {
     UnknownVariant(ControlValue),
 }
 /// A value of a control or a property.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ControlValue {
     None,
     Bool(SmallVec<[bool; 1]>),
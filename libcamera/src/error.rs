@@ -0,0 +1,103 @@
+use std::{io, os::raw::c_int};
+
+use thiserror::Error;
+
+/// Error returned by fallible libcamera operations.
+///
+/// libcamera reports failures as negative errno values, which are mapped to the most common causes. Each variant
+/// carries the name of the operation that failed.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// Camera or resource is already in use (`EBUSY`), e.g. camera is acquired by another process.
+    #[error("{op}: device or resource busy")]
+    Busy { op: &'static str },
+    /// Operation is not permitted in the current camera state (`EACCES`), e.g. configuring a running camera.
+    #[error("{op}: operation not permitted in the current camera state")]
+    InvalidState { op: &'static str },
+    /// Camera has been disconnected from the system (`ENODEV`).
+    #[error("{op}: camera disconnected")]
+    Disconnected { op: &'static str },
+    /// Configuration or argument was rejected (`EINVAL`).
+    #[error("{op}: invalid configuration or argument")]
+    InvalidConfiguration { op: &'static str },
+    /// Not enough memory to complete the operation (`ENOMEM`).
+    #[error("{op}: out of memory")]
+    NoMemory { op: &'static str },
+    /// Operation did not complete in time (`ETIMEDOUT`).
+    #[error("{op}: timed out")]
+    Timeout { op: &'static str },
+    /// Request was cancelled before it completed, e.g. because the camera was stopped.
+    #[error("{op}: request cancelled")]
+    Cancelled { op: &'static str },
+    /// User callback panicked while being executed in the libcamera thread context.
+    #[error("{op}: {message}")]
+    CallbackPanicked { op: &'static str, message: String },
+    /// libcamera completed a request, which was not queued through this camera.
+    #[error("{op}: completed request was not queued by this camera")]
+    UnknownRequest { op: &'static str },
+    /// Any other OS error.
+    #[error("{op}: {source}")]
+    Os {
+        op: &'static str,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl Error {
+    /// Creates error from a (positive) errno value returned by operation `op`.
+    pub fn from_errno(op: &'static str, errno: c_int) -> Self {
+        match errno {
+            libc::EBUSY => Self::Busy { op },
+            libc::EACCES => Self::InvalidState { op },
+            libc::ENODEV => Self::Disconnected { op },
+            libc::EINVAL => Self::InvalidConfiguration { op },
+            libc::ENOMEM => Self::NoMemory { op },
+            libc::ETIMEDOUT => Self::Timeout { op },
+            _ => Self::Os {
+                op,
+                source: io::Error::from_raw_os_error(errno),
+            },
+        }
+    }
+
+    /// Converts libcamera return value of operation `op` into [Result], treating negative values as errors.
+    pub(crate) fn check(op: &'static str, ret: c_int) -> Result<(), Self> {
+        if ret < 0 {
+            Err(Self::from_errno(op, -ret))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Name of the operation that failed.
+    pub fn op(&self) -> &'static str {
+        match self {
+            Self::Busy { op }
+            | Self::InvalidState { op }
+            | Self::Disconnected { op }
+            | Self::InvalidConfiguration { op }
+            | Self::NoMemory { op }
+            | Self::Timeout { op }
+            | Self::Cancelled { op }
+            | Self::CallbackPanicked { op, .. }
+            | Self::UnknownRequest { op }
+            | Self::Os { op, .. } => op,
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        let kind = match &e {
+            Error::Disconnected { .. } => io::ErrorKind::NotConnected,
+            Error::InvalidConfiguration { .. } => io::ErrorKind::InvalidInput,
+            Error::NoMemory { .. } => io::ErrorKind::OutOfMemory,
+            Error::Timeout { .. } => io::ErrorKind::TimedOut,
+            Error::Os { source, .. } => source.kind(),
+            _ => io::ErrorKind::Other,
+        };
+        io::Error::new(kind, e)
+    }
+}
@@ -0,0 +1,110 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{
+    camera::{QueueRequestError, RunningCamera},
+    capture_session::SessionQueue,
+    request::{Request, ReuseFlag},
+    stream::StreamInfo,
+};
+
+/// Completed capture request, which is queued back to the camera when dropped.
+///
+/// Frame dereferences to the completed [Request], so its metadata and buffers can be accessed directly. Once the frame
+/// goes out of scope, the request is reset with [ReuseFlag::REUSE_BUFFERS] and queued again, which keeps the number
+/// of requests in flight constant. Use [Frame::detach()] to keep the request instead.
+///
+/// If the automatic requeue fails, frames of a [CaptureSession](crate::capture_session::CaptureSession) hand the error
+/// and the request over to the session, which returns them on its next call. Otherwise, the error can not be reported
+/// and the request is dropped together with its buffers. Use [Frame::requeue()] to handle errors explicitly.
+pub struct Frame<'a, 'd> {
+    /// Always `Some` until the frame is requeued or detached.
+    req: Option<Request>,
+    cam: &'a RunningCamera<'d>,
+    streams: &'a [StreamInfo],
+    /// Request bookkeeping of the owning [CaptureSession](crate::capture_session::CaptureSession).
+    session: Option<&'a SessionQueue>,
+}
+
+impl<'a, 'd> Frame<'a, 'd> {
+    /// Wraps completed request of camera `cam`.
+    ///
+    /// # Arguments
+    ///
+    /// * `cam` - Camera to queue the request to when the frame is dropped.
+    /// * `req` - Completed request.
+    /// * `streams` - Configuration of streams, which have buffers attached to the request.
+    pub fn new(cam: &'a RunningCamera<'d>, req: Request, streams: &'a [StreamInfo]) -> Self {
+        Self {
+            req: Some(req),
+            cam,
+            streams,
+            session: None,
+        }
+    }
+
+    pub(crate) fn with_session(mut self, session: &'a SessionQueue) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    /// Returns configuration of streams, which have buffers attached to the request.
+    pub fn streams(&self) -> &'a [StreamInfo] {
+        self.streams
+    }
+
+    /// Returns the underlying request without queueing it back to the camera.
+    pub fn detach(mut self) -> Request {
+        self.req.take().unwrap()
+    }
+
+    /// Resets and queues the request back to the camera.
+    ///
+    /// This is what happens when the frame is dropped, except that the error is returned.
+    pub fn requeue(mut self) -> Result<(), QueueRequestError> {
+        self.requeue_inner()
+    }
+
+    fn requeue_inner(&mut self) -> Result<(), QueueRequestError> {
+        let Some(mut req) = self.req.take() else {
+            return Ok(());
+        };
+
+        req.reuse(ReuseFlag::REUSE_BUFFERS);
+        self.cam.queue_request(req)?;
+        if let Some(session) = self.session {
+            session.in_flight.set(session.in_flight.get() + 1);
+        }
+        Ok(())
+    }
+}
+
+impl Deref for Frame<'_, '_> {
+    type Target = Request;
+
+    fn deref(&self) -> &Self::Target {
+        self.req.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for Frame<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.req.as_mut().unwrap()
+    }
+}
+
+impl Drop for Frame<'_, '_> {
+    fn drop(&mut self) {
+        if let (Err(e), Some(session)) = (self.requeue_inner(), self.session) {
+            session.rejected.borrow_mut().push_back(e);
+        }
+    }
+}
+
+impl core::fmt::Debug for Frame<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Frame")
+            .field("request", &self.req)
+            .field("streams", &self.streams)
+            .finish()
+    }
+}
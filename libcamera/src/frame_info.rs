@@ -0,0 +1,135 @@
+use crate::{control::control_name, controls, request::Request};
+
+/// State of the auto exposure or auto white balance algorithm, as reported by `AeState` and `AwbState` metadata.
+///
+/// The reported controls differ between libcamera versions, this type only keeps the states common to them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConvergenceState {
+    /// Algorithm is idle or disabled.
+    Idle,
+    /// Algorithm is still adjusting parameters.
+    Searching,
+    /// Algorithm has converged.
+    Converged,
+    /// Algorithm parameters are locked.
+    Locked,
+    /// Any other state reported by the camera.
+    Other(i32),
+}
+
+impl From<i32> for ConvergenceState {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Idle,
+            1 => Self::Searching,
+            2 => Self::Converged,
+            3 => Self::Locked,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+/// State of the autofocus algorithm, as reported by `AfState` metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FocusState {
+    /// Autofocus is idle or in manual mode.
+    Idle,
+    /// Autofocus scan is in progress.
+    Scanning,
+    /// Autofocus scan has succeeded.
+    Focused,
+    /// Autofocus scan has failed.
+    Failed,
+    /// Any other state reported by the camera.
+    Other(i32),
+}
+
+impl From<i32> for FocusState {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Idle,
+            1 => Self::Scanning,
+            2 => Self::Focused,
+            3 => Self::Failed,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+/// Summary of the commonly used metadata of a completed [Request].
+///
+/// Fields are `None` if the camera does not report the corresponding control.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameInfo {
+    /// Sequence number of the frame, see [Request::sequence()].
+    pub sequence: u32,
+    /// Time of the start of exposure of the first line, in nanoseconds of `CLOCK_BOOTTIME`.
+    pub sensor_timestamp: Option<i64>,
+    /// Same as `sensor_timestamp`, but in nanoseconds of `CLOCK_REALTIME`. Only available with libcamera 0.5.2 and
+    /// newer.
+    pub frame_wall_clock: Option<i64>,
+    /// Exposure time in microseconds.
+    pub exposure_time: Option<i32>,
+    pub analogue_gain: Option<f32>,
+    pub digital_gain: Option<f32>,
+    /// Estimated colour temperature in kelvin.
+    pub colour_temperature: Option<i32>,
+    /// Red and blue gains applied by auto white balance.
+    pub colour_gains: Option<[f32; 2]>,
+    /// Estimated scene illuminance in lux.
+    pub lux: Option<f32>,
+    pub ae_state: Option<ConvergenceState>,
+    pub af_state: Option<FocusState>,
+    pub awb_state: Option<ConvergenceState>,
+    /// Lens position in dioptres.
+    pub lens_position: Option<f32>,
+    /// Frame duration in microseconds.
+    pub frame_duration: Option<i64>,
+}
+
+impl FrameInfo {
+    /// Collects metadata of a completed request.
+    pub fn from_request(req: &Request) -> Self {
+        let metadata = req.metadata();
+        // `AeState` and `AwbState` are draft controls in some of the supported libcamera versions, see
+        // `control_name()`.
+        let draft = |name: &str| {
+            metadata
+                .into_iter()
+                .find(|&(id, _)| control_name(id) == Some(name))
+                .and_then(|(_, val)| i32::try_from(val).ok())
+        };
+
+        Self {
+            sequence: req.sequence(),
+            sensor_timestamp: metadata.get::<controls::SensorTimestamp>().ok().map(|v| *v),
+            #[cfg(libcamera_frame_wall_clock)]
+            frame_wall_clock: metadata.get::<controls::FrameWallClock>().ok().map(|v| *v),
+            #[cfg(not(libcamera_frame_wall_clock))]
+            frame_wall_clock: None,
+            exposure_time: metadata.get::<controls::ExposureTime>().ok().map(|v| *v),
+            analogue_gain: metadata.get::<controls::AnalogueGain>().ok().map(|v| *v),
+            digital_gain: metadata.get::<controls::DigitalGain>().ok().map(|v| *v),
+            colour_temperature: metadata.get::<controls::ColourTemperature>().ok().map(|v| *v),
+            colour_gains: metadata.get::<controls::ColourGains>().ok().map(|v| *v),
+            lux: metadata.get::<controls::Lux>().ok().map(|v| *v),
+            ae_state: draft("AeState").map(ConvergenceState::from),
+            af_state: metadata
+                .get::<controls::AfState>()
+                .ok()
+                .map(|v| FocusState::from(i32::from(v))),
+            awb_state: draft("AwbState").map(ConvergenceState::from),
+            lens_position: metadata.get::<controls::LensPosition>().ok().map(|v| *v),
+            frame_duration: metadata.get::<controls::FrameDuration>().ok().map(|v| *v),
+        }
+    }
+}
+
+impl From<&Request> for FrameInfo {
+    fn from(req: &Request) -> Self {
+        Self::from_request(req)
+    }
+}
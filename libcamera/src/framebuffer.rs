@@ -299,3 +299,37 @@ pub trait AsFrameBuffer: Send {
         }
     }
 }
+
+/// Borrowed reference to a framebuffer owned by libcamera or by the [Request](crate::request::Request).
+///
+/// Used to expose framebuffers in callbacks where the concrete buffer type is unknown.
+pub struct FrameBufferRef<'d> {
+    ptr: NonNull<libcamera_framebuffer_t>,
+    _phantom: PhantomData<&'d ()>,
+}
+
+impl FrameBufferRef<'_> {
+    pub(crate) unsafe fn from_ptr(ptr: NonNull<libcamera_framebuffer_t>) -> Self {
+        Self {
+            ptr,
+            _phantom: Default::default(),
+        }
+    }
+}
+
+impl core::fmt::Debug for FrameBufferRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrameBufferRef")
+            .field("metadata", &self.metadata())
+            .field("planes", &self.planes())
+            .finish()
+    }
+}
+
+unsafe impl Send for FrameBufferRef<'_> {}
+
+impl AsFrameBuffer for FrameBufferRef<'_> {
+    unsafe fn ptr(&self) -> NonNull<libcamera_framebuffer_t> {
+        self.ptr
+    }
+}
@@ -1,12 +1,11 @@
 use std::{
-    io,
     ptr::NonNull,
     sync::{Arc, Mutex},
 };
 
 use libcamera_sys::*;
 
-use crate::{camera::Camera, framebuffer::AsFrameBuffer, stream::Stream};
+use crate::{camera::Camera, error::Error, framebuffer::AsFrameBuffer, stream::Stream};
 
 /// Buffers are stored inside `libcamera_framebuffer_allocator_t` so we use Arc<FrameBufferAllocatorInstance>
 /// to keep the allocator alive as long as there are active buffers.
@@ -48,12 +47,12 @@ impl FrameBufferAllocator {
 
     /// Allocate N buffers for a given stream, where N is equal to
     /// [StreamConfigurationRef::get_buffer_count()](crate::stream::StreamConfigurationRef::get_buffer_count).
-    pub fn alloc(&mut self, stream: &Stream) -> io::Result<Vec<FrameBuffer>> {
+    pub fn alloc(&mut self, stream: &Stream) -> Result<Vec<FrameBuffer>, Error> {
         let mut inner = self.inner.lock().unwrap();
 
         let ret = unsafe { libcamera_framebuffer_allocator_allocate(inner.ptr.as_ptr(), stream.ptr.as_ptr()) };
         if ret < 0 {
-            Err(io::Error::from_raw_os_error(ret))
+            Err(Error::from_errno("FrameBufferAllocator::alloc", -ret))
         } else {
             inner.allocated_streams.push(stream.ptr);
 
@@ -1,7 +1,8 @@
 use libcamera_sys::*;
 
 /// Represents `libcamera::Point`
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub x: i32,
     pub y: i32,
@@ -14,7 +15,8 @@ impl From<libcamera_point_t> for Point {
 }
 
 /// Represents `libcamera::Size`
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Size {
     pub width: u32,
     pub height: u32,
@@ -39,7 +41,8 @@ impl From<Size> for libcamera_size_t {
 }
 
 /// Represents `libcamera::SizeRange`
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SizeRange {
     pub min: Size,
     pub max: Size,
@@ -70,7 +73,8 @@ impl From<SizeRange> for libcamera_size_range_t {
 }
 
 /// Represents `libcamera::Rectangle`
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rectangle {
     pub x: i32,
     pub y: i32,
@@ -2,17 +2,33 @@
 
 pub mod camera;
 pub mod camera_manager;
+pub mod capabilities;
+pub mod capture_session;
+pub mod color_space;
+pub mod configuration_spec;
 pub mod control;
+pub mod control_scheduler;
 pub mod control_value;
+pub mod error;
+pub mod frame;
+pub mod frame_info;
 pub mod framebuffer;
 pub mod framebuffer_allocator;
 pub mod framebuffer_map;
 pub mod geometry;
 pub mod logging;
+pub mod orientation;
 pub mod pixel_format;
 pub mod request;
+pub mod request_pool;
+#[cfg(feature = "async")]
+pub mod request_stream;
+pub mod still_capture;
 pub mod stream;
+pub mod sync_group;
 pub mod utils;
+pub mod validation;
 
 mod generated;
+pub use error::Error;
 pub use generated::*;
@@ -0,0 +1,133 @@
+use libcamera_sys::*;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+/// Image orientation, expressed as one of the eight EXIF orientations.
+///
+/// Each orientation is a rotation by a multiple of 90 degrees clockwise, optionally followed by a horizontal mirror.
+/// Numeric values match the EXIF `Orientation` tag.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash, TryFromPrimitive, IntoPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u32)]
+pub enum Orientation {
+    #[default]
+    Rotate0 = libcamera_orientation::LIBCAMERA_ORIENTATION_ROTATE_0,
+    Rotate0Mirror = libcamera_orientation::LIBCAMERA_ORIENTATION_ROTATE_0_MIRROR,
+    Rotate180 = libcamera_orientation::LIBCAMERA_ORIENTATION_ROTATE_180,
+    Rotate180Mirror = libcamera_orientation::LIBCAMERA_ORIENTATION_ROTATE_180_MIRROR,
+    Rotate90Mirror = libcamera_orientation::LIBCAMERA_ORIENTATION_ROTATE_90_MIRROR,
+    Rotate270 = libcamera_orientation::LIBCAMERA_ORIENTATION_ROTATE_270,
+    Rotate270Mirror = libcamera_orientation::LIBCAMERA_ORIENTATION_ROTATE_270_MIRROR,
+    Rotate90 = libcamera_orientation::LIBCAMERA_ORIENTATION_ROTATE_90,
+}
+
+impl Orientation {
+    /// Returns orientation for a clockwise rotation by `degrees`, optionally followed by a horizontal mirror.
+    ///
+    /// Returns `None` if `degrees` is not a multiple of 90. Negative and larger than 360 degree rotations are
+    /// normalized, which allows passing the `Rotation` camera property directly.
+    pub fn from_rotation(degrees: i32, mirror: bool) -> Option<Self> {
+        if degrees % 90 != 0 {
+            return None;
+        }
+        Some(Self::from_parts(degrees.rem_euclid(360) / 90, mirror))
+    }
+
+    /// Returns clockwise rotation in degrees, which is applied before the mirror.
+    pub fn rotation(self) -> i32 {
+        self.parts().0 * 90
+    }
+
+    /// Returns `true` if the orientation includes a horizontal mirror.
+    pub fn is_mirrored(self) -> bool {
+        self.parts().1
+    }
+
+    /// Returns orientation equivalent to applying `self` first and `other` afterwards.
+    pub fn compose(self, other: Self) -> Self {
+        let (k1, m1) = self.parts();
+        let (k2, m2) = other.parts();
+        // Mirroring reverses the direction of the rotations applied after it.
+        let k2 = if m1 { -k2 } else { k2 };
+        Self::from_parts(k1 + k2, m1 ^ m2)
+    }
+
+    /// Returns orientation which undoes `self`, so that `self.compose(self.inverse())` is [Orientation::Rotate0].
+    pub fn inverse(self) -> Self {
+        match self.parts() {
+            // Mirrored orientations are reflections, which are their own inverse.
+            (_, true) => self,
+            (k, false) => Self::from_parts(-k, false),
+        }
+    }
+
+    /// Returns number of clockwise quarter turns and whether the horizontal mirror is applied afterwards.
+    fn parts(self) -> (i32, bool) {
+        match self {
+            Self::Rotate0 => (0, false),
+            Self::Rotate0Mirror => (0, true),
+            Self::Rotate90 => (1, false),
+            Self::Rotate90Mirror => (1, true),
+            Self::Rotate180 => (2, false),
+            Self::Rotate180Mirror => (2, true),
+            Self::Rotate270 => (3, false),
+            Self::Rotate270Mirror => (3, true),
+        }
+    }
+
+    fn from_parts(quarter_turns: i32, mirror: bool) -> Self {
+        match (quarter_turns.rem_euclid(4), mirror) {
+            (0, false) => Self::Rotate0,
+            (0, true) => Self::Rotate0Mirror,
+            (1, false) => Self::Rotate90,
+            (1, true) => Self::Rotate90Mirror,
+            (2, false) => Self::Rotate180,
+            (2, true) => Self::Rotate180Mirror,
+            (3, false) => Self::Rotate270,
+            _ => Self::Rotate270Mirror,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Orientation; 8] = [
+        Orientation::Rotate0,
+        Orientation::Rotate0Mirror,
+        Orientation::Rotate90,
+        Orientation::Rotate90Mirror,
+        Orientation::Rotate180,
+        Orientation::Rotate180Mirror,
+        Orientation::Rotate270,
+        Orientation::Rotate270Mirror,
+    ];
+
+    #[test]
+    fn compose_with_inverse_is_identity() {
+        for o in ALL {
+            assert_eq!(o.compose(o.inverse()), Orientation::Rotate0, "{o:?}");
+            assert_eq!(o.inverse().compose(o), Orientation::Rotate0, "{o:?}");
+        }
+    }
+
+    #[test]
+    fn compose_is_associative() {
+        for a in ALL {
+            for b in ALL {
+                for c in ALL {
+                    assert_eq!(a.compose(b).compose(c), a.compose(b.compose(c)), "{a:?} {b:?} {c:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rotation_round_trip() {
+        for o in ALL {
+            assert_eq!(Orientation::from_rotation(o.rotation(), o.is_mirrored()), Some(o));
+        }
+        assert_eq!(Orientation::from_rotation(-90, false), Some(Orientation::Rotate270));
+        assert_eq!(Orientation::from_rotation(45, false), None);
+    }
+}
@@ -1,7 +1,12 @@
-use std::{ffi::CStr, ptr::NonNull};
+use std::{
+    ffi::{CStr, CString},
+    ptr::NonNull,
+    str::FromStr,
+};
 
 use drm_fourcc::{DrmFormat, DrmFourcc, DrmModifier};
 use libcamera_sys::*;
+use thiserror::Error;
 
 /// Represents `libcamera::PixelFormat`, which itself is a pair of fourcc code and u64 modifier as defined in `libdrm`.
 #[derive(Clone, Copy)]
@@ -57,6 +62,47 @@ impl core::fmt::Debug for PixelFormat {
     }
 }
 
+impl core::fmt::Display for PixelFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Error returned when parsing a [PixelFormat] from string fails.
+#[derive(Debug, Clone, Error)]
+#[error("unknown pixel format: {0:?}")]
+pub struct ParsePixelFormatError(String);
+
+impl FromStr for PixelFormat {
+    type Err = ParsePixelFormatError;
+
+    /// Parses pixel format from libcamera name, such as `YUV420` or `MJPEG`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParsePixelFormatError(s.to_string());
+        let name = CString::new(s).map_err(|_| err())?;
+        let format = PixelFormat(unsafe { libcamera_pixel_format_from_str(name.as_ptr()) });
+        if format.fourcc() == 0 {
+            return Err(err());
+        }
+        Ok(format)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PixelFormat {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PixelFormat {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 impl TryFrom<PixelFormat> for DrmFormat {
     type Error = drm_fourcc::UnrecognizedFourcc;
 
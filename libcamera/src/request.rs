@@ -1,11 +1,11 @@
 #![allow(clippy::manual_strip)]
 
-use std::{any::Any, collections::HashMap, io, ptr::NonNull};
+use std::{any::Any, collections::HashMap, ptr::NonNull};
 
 use bitflags::bitflags;
 use libcamera_sys::*;
 
-use crate::{control::ControlList, framebuffer::AsFrameBuffer, stream::Stream};
+use crate::{control::ControlList, error::Error, framebuffer::AsFrameBuffer, stream::Stream};
 
 /// Status of [Request]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,7 +14,8 @@ pub enum RequestStatus {
     Pending,
     /// Request was executed successfully
     Complete,
-    /// Request was cancelled, most likely due to call to [ActiveCamera::stop()](crate::camera::ActiveCamera::stop)
+    /// Request was cancelled, most likely due to call to [ActiveCamera::stop()](crate::camera::ActiveCamera::stop) or
+    /// camera being disconnected
     Cancelled,
 }
 
@@ -39,6 +40,27 @@ bitflags! {
     }
 }
 
+/// Framebuffer attached to a [Request], which can be accessed both as its concrete type and generically.
+trait RequestBuffer: AsFrameBuffer + Any {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn as_frame_buffer(&self) -> &dyn AsFrameBuffer;
+}
+
+impl<T: AsFrameBuffer + Any> RequestBuffer for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn as_frame_buffer(&self) -> &dyn AsFrameBuffer {
+        self
+    }
+}
+
 /// A camera capture request.
 ///
 /// Capture requests are created by [ActiveCamera::create_request()](crate::camera::ActiveCamera::create_request)
@@ -48,7 +70,9 @@ bitflags! {
 /// reused by calling [ActiveCamera::queue_request()](crate::camera::ActiveCamera::queue_request) again.
 pub struct Request {
     pub(crate) ptr: NonNull<libcamera_request_t>,
-    buffers: HashMap<Stream, Box<dyn Any + 'static>>,
+    buffers: HashMap<Stream, Box<dyn RequestBuffer>>,
+    /// Set when the request was returned by libcamera after the camera was disconnected.
+    pub(crate) cancelled: bool,
 }
 
 impl Request {
@@ -56,6 +80,7 @@ impl Request {
         Self {
             ptr,
             buffers: Default::default(),
+            cancelled: false,
         }
     }
 
@@ -84,11 +109,11 @@ impl Request {
     ///
     /// Buffers can only be attached once. To access framebuffer after executing request use [Self::buffer()] or
     /// [Self::buffer_mut()].
-    pub fn add_buffer<T: AsFrameBuffer + Any>(&mut self, stream: &Stream, buffer: T) -> io::Result<()> {
+    pub fn add_buffer<T: AsFrameBuffer + Any>(&mut self, stream: &Stream, buffer: T) -> Result<(), Error> {
         let ret =
             unsafe { libcamera_request_add_buffer(self.ptr.as_ptr(), stream.ptr.as_ptr(), buffer.ptr().as_ptr()) };
         if ret < 0 {
-            Err(io::Error::from_raw_os_error(ret))
+            Err(Error::from_errno("Request::add_buffer", -ret))
         } else {
             self.buffers.insert(*stream, Box::new(buffer));
             Ok(())
@@ -99,14 +124,40 @@ impl Request {
     ///
     /// `T` must be equal to the type used in [Self::add_buffer()], otherwise this will return None.
     pub fn buffer<T: 'static>(&self, stream: &Stream) -> Option<&T> {
-        self.buffers.get(stream).and_then(|b| b.downcast_ref())
+        self.buffers
+            .get(stream)
+            .and_then(|b| b.as_ref().as_any().downcast_ref())
     }
 
     /// Returns a mutable reference to the buffer that was attached with [Self::add_buffer()].
     ///
     /// `T` must be equal to the type used in [Self::add_buffer()], otherwise this will return None.
     pub fn buffer_mut<T: 'static>(&mut self, stream: &Stream) -> Option<&mut T> {
-        self.buffers.get_mut(stream).and_then(|b| b.downcast_mut())
+        self.buffers
+            .get_mut(stream)
+            .and_then(|b| b.as_mut().as_any_mut().downcast_mut())
+    }
+
+    /// Returns the buffer attached to `stream` without knowing its concrete type.
+    pub fn frame_buffer(&self, stream: &Stream) -> Option<&dyn AsFrameBuffer> {
+        self.buffers.get(stream).map(|b| b.as_ref().as_frame_buffer())
+    }
+
+    /// Returns streams which have a buffer attached to the request, in the order used by libcamera.
+    pub fn streams(&self) -> Vec<Stream> {
+        self.buffers().map(|(stream, _)| stream).collect()
+    }
+
+    /// Returns an iterator over all attached buffers and their streams, in the order used by libcamera.
+    ///
+    /// This allows processing every stream of a completed request without knowing the concrete buffer types passed
+    /// to [Self::add_buffer()].
+    pub fn buffers(&self) -> RequestBuffersIter<'_> {
+        let map = unsafe { libcamera_request_buffers(self.ptr.as_ptr()) };
+        RequestBuffersIter {
+            it: NonNull::new(unsafe { libcamera_request_buffer_map_iter(map.cast_mut()) }).unwrap(),
+            buffers: &self.buffers,
+        }
     }
 
     /// Returns auto-incrementing sequence number of the capture
@@ -124,6 +175,10 @@ impl Request {
 
     /// Capture request status
     pub fn status(&self) -> RequestStatus {
+        if self.cancelled {
+            return RequestStatus::Cancelled;
+        }
+
         RequestStatus::try_from(unsafe { libcamera_request_status(self.ptr.as_ptr()) }).unwrap()
     }
 
@@ -134,6 +189,7 @@ impl Request {
     /// constructing a new request. The application can reuse the buffers that were previously added to the request
     /// via [Self::add_buffer()] by setting flags to [ReuseFlag::REUSE_BUFFERS].
     pub fn reuse(&mut self, flags: ReuseFlag) {
+        self.cancelled = false;
         unsafe { libcamera_request_reuse(self.ptr.as_ptr(), flags.bits()) }
     }
 }
@@ -155,3 +211,39 @@ impl Drop for Request {
 }
 
 unsafe impl Send for Request {}
+
+/// Iterator over buffers attached to a [Request], see [Request::buffers()].
+pub struct RequestBuffersIter<'d> {
+    it: NonNull<libcamera_request_buffer_map_iter_t>,
+    buffers: &'d HashMap<Stream, Box<dyn RequestBuffer>>,
+}
+
+impl<'d> Iterator for RequestBuffersIter<'d> {
+    type Item = (Stream, &'d dyn AsFrameBuffer);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !unsafe { libcamera_request_buffer_map_iter_end(self.it.as_ptr()) } {
+            let stream = unsafe { libcamera_request_buffer_map_iter_stream(self.it.as_ptr()) };
+            unsafe { libcamera_request_buffer_map_iter_next(self.it.as_ptr()) };
+
+            let stream = unsafe { Stream::from_ptr(NonNull::new(stream.cast_mut()).unwrap()) };
+            // Every buffer is attached with Request::add_buffer(), so this lookup only fails if libcamera added one.
+            if let Some(buffer) = self.buffers.get(&stream) {
+                return Some((stream, buffer.as_ref().as_frame_buffer()));
+            }
+        }
+        None
+    }
+}
+
+impl Drop for RequestBuffersIter<'_> {
+    fn drop(&mut self) {
+        unsafe { libcamera_request_buffer_map_iter_destroy(self.it.as_ptr()) }
+    }
+}
+
+impl core::fmt::Debug for RequestBuffersIter<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestBuffersIter").finish_non_exhaustive()
+    }
+}
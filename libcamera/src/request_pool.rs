@@ -0,0 +1,204 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    ptr::NonNull,
+};
+
+use libcamera_sys::*;
+
+use crate::{
+    camera::{ActiveCamera, RunningCamera},
+    error::Error,
+    framebuffer_allocator::{FrameBuffer, FrameBufferAllocator},
+    framebuffer_map::MemoryMappedFrameBuffer,
+    request::{Request, ReuseFlag},
+    stream::Stream,
+};
+
+/// Framebuffer type attached to requests of a [RequestPool].
+///
+/// Use it to access buffers of completed requests with [Request::buffer()].
+pub type PoolFrameBuffer = MemoryMappedFrameBuffer<FrameBuffer>;
+
+/// A fixed set of requests, each bound to one buffer of every stream.
+///
+/// Requests are identified by their cookie, which is assigned by the pool, and the underlying libcamera request, so
+/// that requests of another pool or camera with the same cookie are not mistaken for its own. Idle requests are handed
+/// out with [RequestPool::acquire()] and given back with [RequestPool::release()] once completed, which resets them
+/// with [ReuseFlag::REUSE_BUFFERS], so that the same buffers are used again.
+///
+/// ```no_run
+/// use libcamera::{camera_manager::CameraManager, request_pool::RequestPool, stream::StreamRole};
+///
+/// let mgr = CameraManager::new().unwrap();
+/// let cameras = mgr.cameras();
+/// let mut cam = cameras.get(0).unwrap().acquire().unwrap();
+/// let mut cfgs = cam
+///     .generate_configuration(&[StreamRole::ViewFinder])
+///     .unwrap();
+/// cam.configure(&mut cfgs).unwrap();
+/// let stream = cfgs.get(0).unwrap().stream().unwrap();
+///
+/// let (tx, rx) = std::sync::mpsc::channel();
+/// cam.on_request_completed(move |req| tx.send(req).unwrap());
+///
+/// let mut pool = RequestPool::new(&mut cam, &[stream], 4).unwrap();
+/// let cam = cam.start(None).unwrap();
+/// pool.queue_all(&cam).unwrap();
+/// for req in rx.iter().take(60) {
+///     println!("Request {} completed", req.cookie());
+///     pool.release(req).unwrap();
+///     let req = pool.acquire().unwrap();
+///     cam.queue_request(req).unwrap();
+/// }
+/// ```
+pub struct RequestPool {
+    streams: Vec<Stream>,
+    /// Requests ready to be queued, in the order they were released.
+    idle: VecDeque<Request>,
+    /// Requests handed out by [RequestPool::acquire()], keyed by cookie.
+    in_flight: HashMap<u64, NonNull<libcamera_request_t>>,
+    len: usize,
+}
+
+impl RequestPool {
+    /// Allocates buffers for `streams` and creates the requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `cam` - Configured camera to create requests for.
+    /// * `streams` - Streams to allocate buffers for, every request gets a single buffer of each.
+    /// * `count` - Number of requests to create. It is limited to the number of buffers allocated for each stream and
+    ///   is at least one.
+    pub fn new<S>(cam: &mut ActiveCamera<'_, S>, streams: &[Stream], count: usize) -> Result<Self, Error> {
+        let idle = VecDeque::from(create_requests(cam, streams, count, "RequestPool::new")?);
+        let len = idle.len();
+
+        Ok(Self {
+            streams: streams.to_vec(),
+            idle,
+            in_flight: HashMap::with_capacity(len),
+            len,
+        })
+    }
+
+    /// Returns streams which have a buffer attached to every request.
+    pub fn streams(&self) -> &[Stream] {
+        &self.streams
+    }
+
+    /// Returns total number of requests in the pool.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the pool has no requests.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns number of requests ready to be acquired.
+    pub fn idle(&self) -> usize {
+        self.idle.len()
+    }
+
+    /// Returns number of acquired requests which have not been released yet.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Returns `true` if request with `cookie` is acquired and has not been released yet.
+    pub fn is_in_flight(&self, cookie: u64) -> bool {
+        self.in_flight.contains_key(&cookie)
+    }
+
+    /// Takes an idle request, which is ready to be queued.
+    ///
+    /// Returns `None` if all requests are in flight.
+    pub fn acquire(&mut self) -> Option<Request> {
+        let req = self.idle.pop_front()?;
+        self.in_flight.insert(req.cookie(), req.ptr);
+        Some(req)
+    }
+
+    /// Resets request with [ReuseFlag::REUSE_BUFFERS] and returns it to the pool.
+    ///
+    /// Requests which do not belong to the pool or are not in flight are returned back as an error.
+    pub fn release(&mut self, mut req: Request) -> Result<(), Request> {
+        if self.in_flight.get(&req.cookie()) != Some(&req.ptr) {
+            return Err(req);
+        }
+        self.in_flight.remove(&req.cookie());
+
+        req.reuse(ReuseFlag::REUSE_BUFFERS);
+        self.idle.push_back(req);
+        Ok(())
+    }
+
+    /// Queues all idle requests to `cam` and returns how many were queued.
+    ///
+    /// If a request is rejected, it is released back to the pool and the error is returned.
+    pub fn queue_all(&mut self, cam: &RunningCamera<'_>) -> Result<usize, Error> {
+        let mut queued = 0;
+        while let Some(req) = self.acquire() {
+            if let Err(e) = cam.queue_request(req) {
+                let _ = self.release(e.request);
+                return Err(e.error);
+            }
+            queued += 1;
+        }
+        Ok(queued)
+    }
+}
+
+// Safety: request pointers of in flight requests are only compared and never dereferenced.
+unsafe impl Send for RequestPool {}
+
+impl core::fmt::Debug for RequestPool {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RequestPool")
+            .field("streams", &self.streams)
+            .field("len", &self.len)
+            .field("idle", &self.idle.len())
+            .field("in_flight", &self.in_flight.len())
+            .finish()
+    }
+}
+
+/// Allocates and memory maps [PoolFrameBuffer]s for `streams` and creates requests, each with a single buffer of every
+/// stream.
+///
+/// Up to `count` requests are created, limited to the number of buffers allocated for each stream, but at least one.
+/// Requests are numbered by cookie from zero. Buffers keep the allocator alive, so it does not have to be kept around.
+pub(crate) fn create_requests<S>(
+    cam: &mut ActiveCamera<'_, S>,
+    streams: &[Stream],
+    count: usize,
+    op: &'static str,
+) -> Result<Vec<Request>, Error> {
+    let mut alloc = FrameBufferAllocator::new(cam);
+    let mut buffers = Vec::with_capacity(streams.len());
+    for stream in streams {
+        let stream_buffers = alloc
+            .alloc(stream)?
+            .into_iter()
+            .map(MemoryMappedFrameBuffer::new)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Error::Os {
+                op,
+                source: io::Error::other(e),
+            })?;
+        buffers.push(stream_buffers.into_iter());
+    }
+
+    let len = buffers.iter().map(|b| b.len()).min().unwrap_or(0).min(count.max(1));
+    let mut reqs = Vec::with_capacity(len);
+    for cookie in 0..len as u64 {
+        let mut req = cam.create_request(Some(cookie)).ok_or(Error::NoMemory { op })?;
+        for (stream, stream_buffers) in streams.iter().zip(buffers.iter_mut()) {
+            req.add_buffer(stream, stream_buffers.next().unwrap())?;
+        }
+        reqs.push(req);
+    }
+    Ok(reqs)
+}
@@ -0,0 +1,123 @@
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+use futures_core::Stream;
+
+use crate::{request::Request, utils};
+
+struct Shared {
+    queue: VecDeque<Request>,
+    capacity: usize,
+    waker: Option<Waker>,
+    /// Set when the sending side (camera callback) is gone and no more requests will arrive.
+    closed: bool,
+    /// Number of completed requests that were discarded because the queue was full.
+    dropped: u64,
+}
+
+/// Sending half of [RequestStream], owned by the request completed callback.
+pub(crate) struct RequestSender {
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl RequestSender {
+    /// Pushes completed request into the queue and wakes up the stream consumer.
+    pub(crate) fn send(&self, req: Request) {
+        let mut shared = utils::lock(&self.shared);
+        if shared.queue.len() >= shared.capacity {
+            // Never block libcamera thread, discard the oldest request instead.
+            shared.queue.pop_front();
+            shared.dropped += 1;
+        }
+        shared.queue.push_back(req);
+
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl Drop for RequestSender {
+    fn drop(&mut self) {
+        let mut shared = utils::lock(&self.shared);
+        shared.closed = true;
+
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Asynchronous stream of completed camera requests.
+///
+/// Obtained by [ActiveCamera::requests()](crate::camera::ActiveCamera::requests). Completed requests are buffered
+/// up to the given capacity. If the consumer falls behind, the oldest buffered requests are dropped, which can be
+/// observed with [RequestStream::dropped()].
+///
+/// The stream ends once the [ActiveCamera](crate::camera::ActiveCamera) is dropped or a different request completed
+/// callback is set.
+pub struct RequestStream {
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl RequestStream {
+    pub(crate) fn new(capacity: usize) -> (RequestSender, Self) {
+        let capacity = capacity.max(1);
+        let shared = Arc::new(Mutex::new(Shared {
+            queue: VecDeque::with_capacity(capacity),
+            capacity,
+            waker: None,
+            closed: false,
+            dropped: 0,
+        }));
+
+        (RequestSender { shared: shared.clone() }, Self { shared })
+    }
+
+    /// Returns the number of completed requests that were discarded because the buffer was full.
+    pub fn dropped(&self) -> u64 {
+        utils::lock(&self.shared).dropped
+    }
+
+    /// Returns the next completed request without waiting, if there is one.
+    pub fn try_next(&mut self) -> Option<Request> {
+        utils::lock(&self.shared).queue.pop_front()
+    }
+}
+
+impl Stream for RequestStream {
+    type Item = Request;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut shared = utils::lock(&self.shared);
+
+        if let Some(req) = shared.queue.pop_front() {
+            Poll::Ready(Some(req))
+        } else if shared.closed {
+            Poll::Ready(None)
+        } else {
+            shared.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (utils::lock(&self.shared).queue.len(), None)
+    }
+}
+
+impl core::fmt::Debug for RequestStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let shared = utils::lock(&self.shared);
+        f.debug_struct("RequestStream")
+            .field("queued", &shared.queue.len())
+            .field("capacity", &shared.capacity)
+            .field("closed", &shared.closed)
+            .field("dropped", &shared.dropped)
+            .finish()
+    }
+}
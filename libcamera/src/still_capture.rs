@@ -0,0 +1,226 @@
+use std::{
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    camera::{ActiveCamera, RunningCamera, Stopped},
+    control::{control_name, ControlList},
+    error::Error,
+    framebuffer::AsFrameBuffer,
+    request::{Request, RequestStatus, ReuseFlag},
+    request_pool::{create_requests, PoolFrameBuffer},
+    stream::{StreamInfo, StreamRole},
+    utils::UniquePtr,
+};
+
+const OP: &str = "ActiveCamera::capture_still";
+
+/// `AeState::Searching` value, which is the same for both draft (libcamera < 0.5) and core `AeState` controls.
+const AE_STATE_SEARCHING: i32 = 1;
+
+/// Frames captured by [ActiveCamera::capture_still()] before the returned one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarmUp {
+    /// Discards the given number of frames, zero returns the first captured frame.
+    Frames(usize),
+    /// Captures frames until auto exposure converges, as reported by `AeState` request metadata. If the camera does
+    /// not report `AeState`, the first frame is returned.
+    AeConverged,
+}
+
+/// A single frame captured by [ActiveCamera::capture_still()].
+#[derive(Debug)]
+pub struct StillCapture {
+    /// Configuration of the captured stream, which describes the layout of plane data.
+    pub stream: StreamInfo,
+    /// Copy of framebuffer planes, truncated to the number of bytes used by each plane.
+    pub planes: Vec<Vec<u8>>,
+    /// Copy of the request metadata.
+    pub metadata: UniquePtr<ControlList>,
+}
+
+/// Error returned when [ActiveCamera::capture_still()] fails.
+///
+/// The camera is given back stopped, so that it is not lost and the capture can be retried.
+#[derive(thiserror::Error)]
+#[error("{error}")]
+pub struct StillCaptureError<'d> {
+    /// Stopped camera, or `None` if the camera failed to stop and has been released.
+    pub camera: Option<ActiveCamera<'d, Stopped>>,
+    /// Underlying error.
+    #[source]
+    pub error: Error,
+}
+
+impl core::fmt::Debug for StillCaptureError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StillCaptureError")
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl From<StillCaptureError<'_>> for Error {
+    fn from(e: StillCaptureError<'_>) -> Self {
+        e.error
+    }
+}
+
+impl<'d> ActiveCamera<'d, Stopped> {
+    /// Captures a single frame.
+    ///
+    /// Configures the camera with a default configuration for the given `role`, allocates buffers and starts capture
+    /// with the given `controls`. Frames are captured until the `warm_up` condition is met, see [WarmUp].
+    ///
+    /// The camera is consumed while capturing and given back stopped together with the frame, left configured for the
+    /// given `role`. Request completed callback and completion queue are not used during capture and are restored
+    /// afterwards. On failure the camera is given back within [StillCaptureError].
+    ///
+    /// Fails with [Error::Timeout] if the frame is not captured within `timeout`, e.g. because auto exposure does not
+    /// converge, and with [Error::Disconnected] if the camera is unplugged during capture.
+    pub fn capture_still(
+        mut self,
+        role: StreamRole,
+        controls: Option<&ControlList>,
+        warm_up: WarmUp,
+        timeout: Duration,
+    ) -> Result<(StillCapture, Self), StillCaptureError<'d>> {
+        let (stream, reqs) = match self.configure_still(role) {
+            Ok(res) => res,
+            Err(error) => {
+                return Err(StillCaptureError {
+                    camera: Some(self),
+                    error,
+                })
+            }
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let (prev_cb, prev_queue) = self.replace_request_handlers(
+            Some(Box::new(move |req| {
+                let _ = tx.send(req);
+            })),
+            None,
+        );
+
+        let (res, mut cam) = match self.start(controls) {
+            Ok(cam) => {
+                let res = capture_frame(&cam, stream, reqs, &rx, warm_up, timeout);
+                // libcamera cancels the remaining requests, they are dropped together with the receiver.
+                match cam.stop() {
+                    Ok(cam) => (res, cam),
+                    Err(e) => {
+                        return Err(StillCaptureError {
+                            camera: None,
+                            error: res.err().unwrap_or(e.error),
+                        })
+                    }
+                }
+            }
+            Err(e) => (Err(e.error), e.camera),
+        };
+
+        cam.replace_request_handlers(prev_cb, prev_queue);
+        match res {
+            Ok(capture) => Ok((capture, cam)),
+            Err(error) => Err(StillCaptureError {
+                camera: Some(cam),
+                error,
+            }),
+        }
+    }
+
+    /// Applies default configuration of `role` and creates a request for every allocated buffer.
+    fn configure_still(&mut self, role: StreamRole) -> Result<(StreamInfo, Vec<Request>), Error> {
+        let mut config = self
+            .generate_configuration(&[role])
+            .ok_or(Error::InvalidConfiguration { op: OP })?;
+        if config.validate().is_invalid() {
+            return Err(Error::InvalidConfiguration { op: OP });
+        }
+        self.configure(&mut config)?;
+        let stream = config
+            .get(0)
+            .and_then(|cfg| cfg.info())
+            .ok_or(Error::InvalidConfiguration { op: OP })?;
+
+        // Every buffer gets its own request, so that frames keep being captured while warming up.
+        let reqs = create_requests(self, &[stream.stream], usize::MAX, OP)?;
+        Ok((stream, reqs))
+    }
+}
+
+fn capture_frame(
+    cam: &RunningCamera<'_>,
+    stream: StreamInfo,
+    reqs: Vec<Request>,
+    rx: &Receiver<Request>,
+    warm_up: WarmUp,
+    timeout: Duration,
+) -> Result<StillCapture, Error> {
+    for req in reqs {
+        cam.queue_request(req)?;
+    }
+
+    let deadline = Instant::now() + timeout;
+    let mut discarded = 0;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let mut req = rx.recv_timeout(remaining).map_err(|e| match e {
+            RecvTimeoutError::Timeout => Error::Timeout { op: OP },
+            // Callback owning the sender is only replaced after capture, so it can only be gone with the camera.
+            RecvTimeoutError::Disconnected => Error::Cancelled { op: OP },
+        })?;
+        match req.status() {
+            RequestStatus::Complete => {}
+            RequestStatus::Cancelled if cam.is_disconnected() => return Err(Error::Disconnected { op: OP }),
+            RequestStatus::Cancelled => return Err(Error::Cancelled { op: OP }),
+            RequestStatus::Pending => return Err(Error::InvalidState { op: OP }),
+        }
+
+        let done = match warm_up {
+            WarmUp::Frames(frames) => discarded >= frames,
+            WarmUp::AeConverged => !req.metadata().into_iter().any(|(id, val)| {
+                control_name(id) == Some("AeState") && i32::try_from(val).is_ok_and(|state| state == AE_STATE_SEARCHING)
+            }),
+        };
+        if done {
+            return Ok(StillCapture {
+                stream,
+                planes: copy_planes(&req, &stream),
+                metadata: copy_metadata(req.metadata()),
+            });
+        }
+
+        discarded += 1;
+        req.reuse(ReuseFlag::REUSE_BUFFERS);
+        cam.queue_request(req)?;
+    }
+}
+
+fn copy_planes(req: &Request, stream: &StreamInfo) -> Vec<Vec<u8>> {
+    let fb: &PoolFrameBuffer = req.buffer(&stream.stream).unwrap();
+    let used = fb.metadata().map(|m| m.planes());
+
+    fb.data()
+        .into_iter()
+        .enumerate()
+        .map(|(i, data)| {
+            let len = used
+                .as_ref()
+                .and_then(|planes| planes.get(i))
+                .map(|plane| (plane.bytes_used as usize).min(data.len()))
+                .unwrap_or(data.len());
+            data[..len].to_vec()
+        })
+        .collect()
+}
+
+fn copy_metadata(metadata: &ControlList) -> UniquePtr<ControlList> {
+    let mut copy = ControlList::new();
+    for (id, val) in metadata {
+        let _ = copy.set_raw(id, val);
+    }
+    copy
+}
@@ -1,8 +1,9 @@
-use std::{marker::PhantomData, ptr::NonNull};
+use std::{marker::PhantomData, mem::MaybeUninit, ptr::NonNull};
 
 use libcamera_sys::*;
 
 use crate::{
+    color_space::ColorSpace,
     geometry::{Size, SizeRange},
     pixel_format::{PixelFormat, PixelFormats},
     utils::Immutable,
@@ -11,7 +12,8 @@ use crate::{
 /// Stream role hint for generating configuration.
 ///
 /// Used in [Camera::generate_configuration()](crate::camera::Camera::generate_configuration).
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StreamRole {
     Raw,
     StillCapture,
@@ -144,6 +146,30 @@ impl StreamConfigurationRef<'_> {
         unsafe { self.ptr.as_mut() }.buffer_count = buffer_count;
     }
 
+    /// Returns the color space of the stream, if one is set.
+    ///
+    /// After [CameraConfiguration::validate()](crate::camera::CameraConfiguration::validate) it reflects the color
+    /// space chosen by the pipeline handler.
+    pub fn get_color_space(&self) -> Option<ColorSpace> {
+        let mut color_space = MaybeUninit::uninit();
+        if unsafe { libcamera_stream_configuration_get_color_space(self.ptr.as_ptr(), color_space.as_mut_ptr()) } {
+            unsafe { color_space.assume_init() }.try_into().ok()
+        } else {
+            None
+        }
+    }
+
+    /// Requests color space of the stream, or lets the pipeline handler choose one if `None`.
+    pub fn set_color_space(&mut self, color_space: Option<ColorSpace>) {
+        let color_space = color_space.map(libcamera_color_space_t::from);
+        unsafe {
+            libcamera_stream_configuration_set_color_space(
+                self.ptr.as_ptr(),
+                color_space.as_ref().map_or(core::ptr::null(), |c| c as *const _),
+            )
+        }
+    }
+
     /// Returns initialized [Stream] for this configuration.
     ///
     /// Stream is only available once this configuration is applied with
@@ -156,6 +182,19 @@ impl StreamConfigurationRef<'_> {
         NonNull::new(stream).map(|p| unsafe { Stream::from_ptr(p) })
     }
 
+    /// Returns a [StreamInfo] snapshot of this configuration.
+    ///
+    /// Same as [Self::stream()], it is only available once this configuration is applied.
+    pub fn info(&self) -> Option<StreamInfo> {
+        Some(StreamInfo {
+            stream: self.stream()?,
+            pixel_format: self.get_pixel_format(),
+            size: self.get_size(),
+            stride: self.get_stride(),
+            frame_size: self.get_frame_size(),
+        })
+    }
+
     /// Returns a list of available stream formats for this configuration.
     pub fn formats(&self) -> StreamFormatsRef<'_> {
         unsafe {
@@ -174,6 +213,7 @@ impl core::fmt::Debug for StreamConfigurationRef<'_> {
             .field("stride", &self.get_stride())
             .field("frame_size", &self.get_frame_size())
             .field("buffer_count", &self.get_buffer_count())
+            .field("color_space", &self.get_color_space())
             .finish()
     }
 }
@@ -181,7 +221,7 @@ impl core::fmt::Debug for StreamConfigurationRef<'_> {
 /// Handle to a camera stream.
 ///
 /// Obtained from [StreamConfigurationRef::stream()] and is valid as long as camera configuration is unchanged.
-#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub struct Stream {
     /// libcamera_stream_t is used as unique key across various libcamera structures
     /// and adding a lifetime would be really inconvenient. Dangling pointer should not
@@ -197,3 +237,16 @@ impl Stream {
 }
 
 unsafe impl Send for Stream {}
+
+/// Copy of an applied [StreamConfigurationRef], which describes the layout of stream framebuffers.
+///
+/// Unlike [StreamConfigurationRef], it does not borrow camera configuration. Obtained from
+/// [StreamConfigurationRef::info()].
+#[derive(Debug, Clone, Copy)]
+pub struct StreamInfo {
+    pub stream: Stream,
+    pub pixel_format: PixelFormat,
+    pub size: Size,
+    pub stride: u32,
+    pub frame_size: u32,
+}
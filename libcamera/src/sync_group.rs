@@ -0,0 +1,488 @@
+use std::{collections::VecDeque, time::Duration};
+
+#[cfg(all(feature = "vendor_rpi", libcamera_sync_controls))]
+use crate::{
+    camera::Camera,
+    control::{ControlEntry, ControlError, ControlList},
+    error::Error,
+    utils::UniquePtr,
+};
+use crate::{
+    controls,
+    request::{Request, RequestStatus},
+};
+
+/// Outcome of pairing completed requests, returned by [SyncGroup::poll()].
+#[derive(Debug, PartialEq)]
+pub enum SyncEvent<T = Request> {
+    /// Requests with sensor timestamps within the tolerance, one for each camera in the order of camera indices.
+    Matched(Vec<T>),
+    /// Request of `camera` for which no counterpart was found, or which did not complete successfully.
+    ///
+    /// Requests are returned so that they can be queued again.
+    Unmatched { camera: usize, request: T },
+    /// `camera` dropped `count` frames, detected as a gap in frame sequence numbers reported in buffer metadata.
+    Dropped { camera: usize, count: u32 },
+}
+
+/// Per-camera counters of a [SyncGroup].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncStats {
+    /// Number of requests returned within [SyncEvent::Matched].
+    pub matched: u64,
+    /// Number of requests returned as [SyncEvent::Unmatched].
+    pub unmatched: u64,
+    /// Number of frames reported as [SyncEvent::Dropped].
+    pub dropped: u64,
+}
+
+struct Pending<T> {
+    timestamp: i64,
+    request: T,
+}
+
+struct CameraQueue<T> {
+    pending: VecDeque<Pending<T>>,
+    last_sequence: Option<u32>,
+    stats: SyncStats,
+}
+
+impl<T> Default for CameraQueue<T> {
+    fn default() -> Self {
+        Self {
+            pending: VecDeque::new(),
+            last_sequence: None,
+            stats: SyncStats::default(),
+        }
+    }
+}
+
+/// Pairing logic of [SyncGroup], which only relies on timestamps and sequence numbers of the grouped items.
+struct Matcher<T> {
+    cameras: Vec<CameraQueue<T>>,
+    /// Tolerance in nanoseconds, which is the unit of `SensorTimestamp`.
+    tolerance: i64,
+    max_pending: usize,
+    events: VecDeque<SyncEvent<T>>,
+}
+
+impl<T> Matcher<T> {
+    fn new(cameras: usize, tolerance: Duration) -> Self {
+        Self {
+            cameras: (0..cameras).map(|_| CameraQueue::default()).collect(),
+            tolerance: tolerance.as_nanos().try_into().unwrap_or(i64::MAX),
+            max_pending: 2,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Adds `request` of `camera` captured at `timestamp` with frame `sequence`.
+    ///
+    /// Requests without a timestamp are returned as unmatched right away.
+    fn push(&mut self, camera: usize, request: T, timestamp: Option<i64>, sequence: Option<u32>) {
+        let queue = &mut self.cameras[camera];
+        if let Some(sequence) = sequence {
+            if let Some(last) = queue.last_sequence {
+                let count = sequence.wrapping_sub(last).wrapping_sub(1);
+                if count > 0 && count < u32::MAX / 2 {
+                    queue.stats.dropped += count as u64;
+                    self.events.push_back(SyncEvent::Dropped { camera, count });
+                }
+            }
+            queue.last_sequence = Some(sequence);
+        }
+
+        match timestamp {
+            Some(timestamp) => {
+                queue.pending.push_back(Pending { timestamp, request });
+                if queue.pending.len() > self.max_pending {
+                    self.unmatched(camera);
+                }
+            }
+            None => {
+                queue.stats.unmatched += 1;
+                self.events.push_back(SyncEvent::Unmatched { camera, request });
+            }
+        }
+
+        self.match_pending();
+    }
+
+    fn drain(&mut self) -> impl Iterator<Item = (usize, T)> + '_ {
+        self.cameras.iter_mut().enumerate().flat_map(|(camera, queue)| {
+            queue.last_sequence = None;
+            queue.pending.drain(..).map(move |p| (camera, p.request))
+        })
+    }
+
+    fn unmatched(&mut self, camera: usize) {
+        let queue = &mut self.cameras[camera];
+        if let Some(pending) = queue.pending.pop_front() {
+            queue.stats.unmatched += 1;
+            self.events.push_back(SyncEvent::Unmatched {
+                camera,
+                request: pending.request,
+            });
+        }
+    }
+
+    /// Emits groups while every camera has a pending request, discarding requests which are too old to be matched.
+    fn match_pending(&mut self) {
+        if self.cameras.is_empty() {
+            return;
+        }
+
+        loop {
+            let heads = self
+                .cameras
+                .iter()
+                .map(|queue| queue.pending.front().map(|p| p.timestamp))
+                .collect::<Option<Vec<_>>>();
+            let Some(heads) = heads else {
+                return;
+            };
+
+            let newest = *heads.iter().max().unwrap();
+            let stale = heads.iter().position(|&ts| newest - ts > self.tolerance);
+            match stale {
+                // Newer requests of the other cameras can not be closer, so the stale request has no counterpart.
+                Some(camera) => self.unmatched(camera),
+                None => {
+                    let requests = self
+                        .cameras
+                        .iter_mut()
+                        .map(|queue| {
+                            queue.stats.matched += 1;
+                            queue.pending.pop_front().unwrap().request
+                        })
+                        .collect();
+                    self.events.push_back(SyncEvent::Matched(requests));
+                }
+            }
+        }
+    }
+}
+
+/// Groups completed requests of several cameras by sensor timestamp.
+///
+/// Completed requests are handed over with [SyncGroup::push()], together with the index of the camera that produced
+/// them. Requests of different cameras are matched if their `SensorTimestamp` metadata differ by at most the
+/// tolerance. Requests which can not be matched, e.g. because the other camera dropped the corresponding frame, are
+/// returned as [SyncEvent::Unmatched], so that they can be queued again.
+///
+/// ```no_run
+/// use std::{sync::mpsc, time::Duration};
+///
+/// use libcamera::{
+///     camera_manager::CameraManager,
+///     sync_group::{SyncEvent, SyncGroup},
+/// };
+///
+/// let mgr = CameraManager::new().unwrap();
+/// let cameras = mgr.cameras();
+/// let (tx, rx) = mpsc::channel();
+///
+/// // Each camera forwards completed requests with its index, configure and start the cameras as usual
+/// let mut left = cameras.get(0).unwrap().acquire().unwrap();
+/// let left_tx = tx.clone();
+/// left.on_request_completed(move |req| left_tx.send((0, req)).unwrap());
+/// let mut right = cameras.get(1).unwrap().acquire().unwrap();
+/// right.on_request_completed(move |req| tx.send((1, req)).unwrap());
+///
+/// let mut group = SyncGroup::new(2, Duration::from_millis(1));
+/// for (camera, req) in rx {
+///     group.push(camera, req);
+///     while let Some(event) = group.poll() {
+///         match event {
+///             SyncEvent::Matched(reqs) => println!("Stereo pair {} {}", reqs[0].sequence(), reqs[1].sequence()),
+///             SyncEvent::Unmatched { camera, request } => {
+///                 println!("Camera {camera} unmatched frame {}", request.sequence())
+///             }
+///             SyncEvent::Dropped { camera, count } => println!("Camera {camera} dropped {count} frames"),
+///         }
+///     }
+/// }
+/// ```
+pub struct SyncGroup {
+    matcher: Matcher<Request>,
+    #[cfg(all(feature = "vendor_rpi", libcamera_sync_controls))]
+    require_sync_ready: bool,
+}
+
+impl SyncGroup {
+    /// Creates a group of `cameras` cameras, which matches requests with sensor timestamps within `tolerance`.
+    pub fn new(cameras: usize, tolerance: Duration) -> Self {
+        Self {
+            matcher: Matcher::new(cameras, tolerance),
+            #[cfg(all(feature = "vendor_rpi", libcamera_sync_controls))]
+            require_sync_ready: false,
+        }
+    }
+
+    /// Sets the number of requests held for each camera while waiting for the other cameras, which defaults to 2.
+    ///
+    /// Once exceeded, the oldest request is returned as [SyncEvent::Unmatched]. Keep it lower than the number of
+    /// requests queued to each camera, otherwise a stalled camera starves the others.
+    pub fn set_max_pending(&mut self, max_pending: usize) {
+        self.matcher.max_pending = max_pending.max(1);
+    }
+
+    /// Treats requests without `SyncReady` metadata set as unmatched, so that only hardware synchronized frames are
+    /// grouped. See [sync_controls()].
+    #[cfg(all(feature = "vendor_rpi", libcamera_sync_controls))]
+    pub fn set_require_sync_ready(&mut self, require_sync_ready: bool) {
+        self.require_sync_ready = require_sync_ready;
+    }
+
+    /// Returns number of cameras in the group.
+    pub fn len(&self) -> usize {
+        self.matcher.cameras.len()
+    }
+
+    /// Returns `true` if the group has no cameras.
+    pub fn is_empty(&self) -> bool {
+        self.matcher.cameras.is_empty()
+    }
+
+    /// Returns counters of `camera`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `camera` is out of bounds.
+    pub fn stats(&self, camera: usize) -> SyncStats {
+        self.matcher.cameras[camera].stats
+    }
+
+    /// Adds completed request of `camera` to the group.
+    ///
+    /// Resulting events are retrieved with [SyncGroup::poll()].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `camera` is out of bounds.
+    pub fn push(&mut self, camera: usize, request: Request) {
+        let timestamp = request
+            .metadata()
+            .get::<controls::SensorTimestamp>()
+            .ok()
+            .filter(|_| request.status() == RequestStatus::Complete && self.is_ready(&request))
+            .map(|timestamp| *timestamp);
+        let sequence = frame_sequence(&request);
+
+        self.matcher.push(camera, request, timestamp, sequence);
+    }
+
+    /// Returns the next event, or `None` if more requests have to be pushed first.
+    pub fn poll(&mut self) -> Option<SyncEvent> {
+        self.matcher.events.pop_front()
+    }
+
+    /// Returns all pending requests waiting for a counterpart, which is useful when stopping the cameras.
+    ///
+    /// Events which have not been polled yet are kept.
+    pub fn drain(&mut self) -> impl Iterator<Item = (usize, Request)> + '_ {
+        self.matcher.drain()
+    }
+
+    #[cfg(all(feature = "vendor_rpi", libcamera_sync_controls))]
+    fn is_ready(&self, request: &Request) -> bool {
+        !self.require_sync_ready || is_sync_ready(request)
+    }
+
+    #[cfg(not(all(feature = "vendor_rpi", libcamera_sync_controls)))]
+    fn is_ready(&self, _request: &Request) -> bool {
+        true
+    }
+}
+
+/// Returns sensor frame sequence number of a completed request, taken from metadata of its buffers.
+///
+/// Unlike [Request::sequence()], which counts queued requests, it advances with every frame captured by the sensor, so
+/// that gaps reveal dropped frames.
+fn frame_sequence(request: &Request) -> Option<u32> {
+    if request.status() != RequestStatus::Complete {
+        return None;
+    }
+    request
+        .buffers()
+        .find_map(|(_, fb)| fb.metadata().map(|metadata| metadata.sequence()))
+}
+
+impl core::fmt::Debug for SyncGroup {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SyncGroup")
+            .field("cameras", &self.matcher.cameras.len())
+            .field("tolerance", &Duration::from_nanos(self.matcher.tolerance as u64))
+            .field(
+                "pending",
+                &self.matcher.cameras.iter().map(|q| q.pending.len()).collect::<Vec<_>>(),
+            )
+            .field("events", &self.matcher.events.len())
+            .finish()
+    }
+}
+
+/// Role of a camera in Raspberry Pi hardware synchronization, see [sync_controls()].
+#[cfg(all(feature = "vendor_rpi", libcamera_sync_controls))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncRole {
+    /// Broadcasts timing information. There must be exactly one server.
+    Server {
+        /// Number of frames to wait before signalling `SyncReady`, or `None` for the pipeline default.
+        frames: Option<i32>,
+    },
+    /// Adjusts its frame timing to match the server.
+    Client,
+}
+
+/// Builds controls which put `cam` into Raspberry Pi hardware synchronization mode.
+///
+/// The controls have to be passed to [ActiveCamera::start()](crate::camera::ActiveCamera::start). All synchronized
+/// cameras must run at the same fixed frame rate. Fails with [Error::InvalidConfiguration] if the camera does not
+/// support synchronization.
+///
+/// Only available when building against libcamera 0.5.2 or newer, which introduced the synchronization controls.
+#[cfg(all(feature = "vendor_rpi", libcamera_sync_controls))]
+pub fn sync_controls(cam: &Camera<'_>, role: SyncRole) -> Result<UniquePtr<ControlList>, Error> {
+    use controls::{SyncFrames, SyncMode};
+
+    const OP: &str = "sync_controls";
+    let invalid = |_: ControlError| Error::InvalidConfiguration { op: OP };
+
+    let mut controls = ControlList::new();
+    cam.controls().find(SyncMode::ID).map_err(invalid)?;
+    match role {
+        SyncRole::Server { frames } => {
+            controls.set(SyncMode::Server).map_err(invalid)?;
+            if let Some(frames) = frames {
+                cam.controls().find(SyncFrames::ID).map_err(invalid)?;
+                controls.set(SyncFrames(frames)).map_err(invalid)?;
+            }
+        }
+        SyncRole::Client => controls.set(SyncMode::Client).map_err(invalid)?,
+    }
+    Ok(controls)
+}
+
+/// Returns `true` if request metadata reports `SyncReady`, which means that the frame is hardware synchronized.
+#[cfg(all(feature = "vendor_rpi", libcamera_sync_controls))]
+pub fn is_sync_ready(request: &Request) -> bool {
+    request
+        .metadata()
+        .get::<controls::SyncReady>()
+        .is_ok_and(|ready| *ready)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: i64 = 1_000_000;
+
+    fn matcher(cameras: usize) -> Matcher<&'static str> {
+        Matcher::new(cameras, Duration::from_millis(1))
+    }
+
+    fn events(matcher: &mut Matcher<&'static str>) -> Vec<SyncEvent<&'static str>> {
+        matcher.events.drain(..).collect()
+    }
+
+    #[test]
+    fn matches_within_tolerance() {
+        let mut m = matcher(2);
+        m.push(0, "l0", Some(10 * MS), Some(0));
+        assert!(events(&mut m).is_empty());
+        m.push(1, "r0", Some(10 * MS + MS / 2), Some(0));
+        assert_eq!(events(&mut m), [SyncEvent::Matched(vec!["l0", "r0"])]);
+        assert_eq!(m.cameras[0].stats.matched, 1);
+        assert_eq!(m.cameras[1].stats.matched, 1);
+    }
+
+    #[test]
+    fn stale_request_is_unmatched() {
+        let mut m = matcher(2);
+        m.push(0, "l0", Some(10 * MS), Some(0));
+        m.push(0, "l1", Some(43 * MS), Some(1));
+        // Right camera missed the first frame
+        m.push(1, "r1", Some(43 * MS), Some(1));
+        assert_eq!(
+            events(&mut m),
+            [
+                SyncEvent::Unmatched {
+                    camera: 0,
+                    request: "l0"
+                },
+                SyncEvent::Matched(vec!["l1", "r1"]),
+            ]
+        );
+        assert_eq!(m.cameras[0].stats.unmatched, 1);
+    }
+
+    #[test]
+    fn reports_dropped_frames() {
+        let mut m = matcher(1);
+        m.push(0, "a", Some(0), Some(5));
+        m.push(0, "b", Some(100 * MS), Some(8));
+        assert_eq!(
+            events(&mut m),
+            [
+                SyncEvent::Matched(vec!["a"]),
+                SyncEvent::Dropped { camera: 0, count: 2 },
+                SyncEvent::Matched(vec!["b"]),
+            ]
+        );
+        assert_eq!(m.cameras[0].stats.dropped, 2);
+    }
+
+    #[test]
+    fn sequence_wraparound_is_not_a_drop() {
+        let mut m = matcher(1);
+        m.push(0, "a", Some(0), Some(u32::MAX));
+        m.push(0, "b", Some(MS), Some(0));
+        // Restarted or reordered sequence numbers are ignored as well
+        m.push(0, "c", Some(2 * MS), Some(0));
+        assert!(events(&mut m)
+            .iter()
+            .all(|event| matches!(event, SyncEvent::Matched(_))));
+        assert_eq!(m.cameras[0].stats.dropped, 0);
+    }
+
+    #[test]
+    fn max_pending_overflow() {
+        let mut m = matcher(2);
+        for (i, name) in ["l0", "l1", "l2"].into_iter().enumerate() {
+            m.push(0, name, Some(i as i64 * 33 * MS), None);
+        }
+        assert_eq!(
+            events(&mut m),
+            [SyncEvent::Unmatched {
+                camera: 0,
+                request: "l0"
+            }]
+        );
+        assert_eq!(m.cameras[0].pending.len(), 2);
+    }
+
+    #[test]
+    fn missing_timestamp_is_unmatched() {
+        let mut m = matcher(2);
+        m.push(1, "r0", None, None);
+        assert_eq!(
+            events(&mut m),
+            [SyncEvent::Unmatched {
+                camera: 1,
+                request: "r0"
+            }]
+        );
+        assert!(m.cameras[1].pending.is_empty());
+    }
+
+    #[test]
+    fn drain_returns_pending() {
+        let mut m = matcher(2);
+        m.push(0, "l0", Some(0), Some(0));
+        m.push(0, "l1", Some(33 * MS), Some(1));
+        assert_eq!(m.drain().collect::<Vec<_>>(), [(0, "l0"), (0, "l1")]);
+        assert!(m.cameras[0].last_sequence.is_none());
+    }
+}
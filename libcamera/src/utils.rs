@@ -3,6 +3,8 @@ use std::{
     io,
     ops::{Deref, DerefMut},
     ptr::NonNull,
+    sync::{Mutex, MutexGuard, PoisonError},
+    thread,
 };
 
 /// Provides only an immutable reference to the contained type T.
@@ -94,8 +96,32 @@ impl<T: UniquePtrTarget + core::fmt::Debug> core::fmt::Debug for UniquePtr<T> {
 #[inline]
 pub fn handle_result(ret: c_int) -> io::Result<()> {
     if ret < 0 {
-        Err(io::Error::from_raw_os_error(ret))
+        Err(io::Error::from_raw_os_error(-ret))
     } else {
         Ok(())
     }
 }
+
+/// Locks `mutex`, recovering its state if the lock has been poisoned.
+///
+/// User callbacks are executed with panics caught, so the state is consistent even if a panic did occur while holding
+/// the lock.
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Stores message of a panic caught in `callback` into `slot`, so that it can be reported to the application later.
+///
+/// Only the first panic is kept until the slot is taken.
+pub(crate) fn record_panic(slot: &mut Option<String>, callback: &str, res: thread::Result<()>) {
+    if let Err(payload) = res {
+        let message = if let Some(s) = payload.downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "unknown panic payload".to_string()
+        };
+        slot.get_or_insert_with(|| format!("{callback} callback panicked: {message}"));
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{
+    camera::{CameraConfiguration, CameraConfigurationStatus, SensorConfiguration},
+    color_space::ColorSpace,
+    geometry::Size,
+    orientation::Orientation,
+    pixel_format::PixelFormat,
+    stream::StreamConfigurationRef,
+};
+
+/// A value adjusted by libcamera during validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change<T> {
+    /// Requested value.
+    pub from: T,
+    /// Value after validation.
+    pub to: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    fn detect(from: T, to: T) -> Option<Self> {
+        (from != to).then_some(Self { from, to })
+    }
+}
+
+/// A single stream configuration field adjusted during validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamFieldChange {
+    PixelFormat(Change<PixelFormat>),
+    Size(Change<Size>),
+    Stride(Change<u32>),
+    BufferCount(Change<u32>),
+    ColorSpace(Change<Option<ColorSpace>>),
+}
+
+/// Adjustment of a stream configuration, see [ValidationReport::streams].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamChange {
+    /// Index of the stream within [CameraConfiguration].
+    pub index: usize,
+    pub change: StreamFieldChange,
+}
+
+/// Outcome of [CameraConfiguration::validate_with_report()], listing every field adjusted by libcamera.
+#[derive(Debug, Clone)]
+pub struct ValidationReport {
+    /// Status returned by libcamera.
+    pub status: CameraConfigurationStatus,
+    /// Adjusted stream configuration fields, ordered by stream index.
+    pub streams: Vec<StreamChange>,
+    /// Number of streams removed from the end of the configuration, because the pipeline can not provide them.
+    pub removed_streams: usize,
+    /// Adjusted sensor configuration.
+    pub sensor_configuration: Option<Change<Option<SensorConfiguration>>>,
+    /// Adjusted orientation.
+    pub orientation: Option<Change<Orientation>>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if libcamera did not change any of the tracked fields.
+    ///
+    /// The status can still be [CameraConfigurationStatus::Adjusted] if libcamera adjusted a field which is not
+    /// tracked by the report, e.g. frame size.
+    pub fn is_empty(&self) -> bool {
+        self.streams.is_empty()
+            && self.removed_streams == 0
+            && self.sensor_configuration.is_none()
+            && self.orientation.is_none()
+    }
+}
+
+/// Snapshot of the stream configuration fields tracked by [ValidationReport].
+struct StreamSnapshot {
+    pixel_format: PixelFormat,
+    size: Size,
+    stride: u32,
+    buffer_count: u32,
+    color_space: Option<ColorSpace>,
+}
+
+impl StreamSnapshot {
+    fn new(cfg: &StreamConfigurationRef<'_>) -> Self {
+        Self {
+            pixel_format: cfg.get_pixel_format(),
+            size: cfg.get_size(),
+            stride: cfg.get_stride(),
+            buffer_count: cfg.get_buffer_count(),
+            color_space: cfg.get_color_space(),
+        }
+    }
+
+    fn diff(self, to: Self, index: usize, out: &mut Vec<StreamChange>) {
+        let changes = [
+            Change::detect(self.pixel_format, to.pixel_format).map(StreamFieldChange::PixelFormat),
+            Change::detect(self.size, to.size).map(StreamFieldChange::Size),
+            Change::detect(self.stride, to.stride).map(StreamFieldChange::Stride),
+            Change::detect(self.buffer_count, to.buffer_count).map(StreamFieldChange::BufferCount),
+            Change::detect(self.color_space, to.color_space).map(StreamFieldChange::ColorSpace),
+        ];
+        out.extend(
+            changes
+                .into_iter()
+                .flatten()
+                .map(|change| StreamChange { index, change }),
+        );
+    }
+}
+
+impl CameraConfiguration {
+    /// Validates camera configuration and reports every field adjusted by libcamera.
+    ///
+    /// Validation is the same as by [CameraConfiguration::validate()], but stream configurations, sensor
+    /// configuration and orientation are snapshotted beforehand and compared with the validated values.
+    pub fn validate_with_report(&mut self) -> ValidationReport {
+        let streams = self.snapshot_streams();
+        let sensor_configuration = self.sensor_configuration();
+        let orientation = self.orientation();
+
+        let status = self.validate();
+
+        let validated = self.snapshot_streams();
+        let removed_streams = streams.len().saturating_sub(validated.len());
+        let mut changes = Vec::new();
+        for (index, (from, to)) in streams.into_iter().zip(validated).enumerate() {
+            from.diff(to, index, &mut changes);
+        }
+
+        ValidationReport {
+            status,
+            streams: changes,
+            removed_streams,
+            sensor_configuration: Change::detect(sensor_configuration, self.sensor_configuration()),
+            orientation: Change::detect(orientation, self.orientation()),
+        }
+    }
+
+    fn snapshot_streams(&self) -> Vec<StreamSnapshot> {
+        (0..self.len())
+            .filter_map(|i| self.get(i))
+            .map(|cfg| StreamSnapshot::new(&cfg))
+            .collect()
+    }
+}